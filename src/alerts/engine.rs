@@ -0,0 +1,370 @@
+use chrono::{DateTime, Duration, Utc};
+use rust_decimal::Decimal;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::time::interval;
+use tracing::{info, warn};
+
+use crate::config::AlertsConfig;
+use crate::metrics::Metrics;
+use crate::models::BestPriceChange;
+use crate::state::AppState;
+
+use super::rules::{AlertCondition, CrossDirection};
+use super::{AlertEvent, AlertRule, AlertRules, WebhookNotifier};
+
+/// Evaluates alert rules against best-price changes and periodic staleness checks
+pub struct AlertEngine {
+    rules: AlertRules,
+    state: AppState,
+    notifier: WebhookNotifier,
+    metrics: Arc<Metrics>,
+    dedup_window: Duration,
+    check_interval_secs: u64,
+    /// Last selected price per pair, for detecting threshold crosses
+    last_prices: HashMap<String, Decimal>,
+    /// Recent selected prices per pair, for percent-move windows
+    history: HashMap<String, VecDeque<(DateTime<Utc>, Decimal)>>,
+    /// Rules currently reporting a stale pair; cleared when the pair recovers
+    stale_rules: HashSet<String>,
+    /// Last firing time per (rule id, dedup key)
+    last_fired: HashMap<(String, String), DateTime<Utc>>,
+}
+
+impl AlertEngine {
+    pub fn new(
+        config: &AlertsConfig,
+        rules: AlertRules,
+        state: AppState,
+        notifier: WebhookNotifier,
+        metrics: Arc<Metrics>,
+    ) -> Self {
+        Self {
+            rules,
+            state,
+            notifier,
+            metrics,
+            dedup_window: Duration::seconds(config.dedup_window_secs as i64),
+            check_interval_secs: config.check_interval_secs,
+            last_prices: HashMap::new(),
+            history: HashMap::new(),
+            stale_rules: HashSet::new(),
+            last_fired: HashMap::new(),
+        }
+    }
+
+    pub async fn run(mut self, mut changes: broadcast::Receiver<BestPriceChange>) {
+        info!(
+            check_interval_secs = self.check_interval_secs,
+            "Starting alert engine"
+        );
+
+        let mut ticker = interval(std::time::Duration::from_secs(self.check_interval_secs));
+
+        loop {
+            tokio::select! {
+                change = changes.recv() => match change {
+                    Ok(change) => self.on_price_change(&change).await,
+                    Err(RecvError::Lagged(skipped)) => {
+                        warn!(skipped, "Alert engine lagging behind, dropped price changes");
+                    }
+                    Err(RecvError::Closed) => break,
+                },
+                _ = ticker.tick() => self.check_staleness().await,
+            }
+        }
+    }
+
+    async fn on_price_change(&mut self, change: &BestPriceChange) {
+        let pair = &change.data.pair;
+        let price = change.data.price;
+        let now = change.data.timestamp;
+        let rules = self.rules.list().await;
+
+        let previous_price = self.last_prices.insert(pair.clone(), price);
+        self.record_history(pair, now, price, &rules);
+
+        for rule in rules.iter().filter(|r| &r.pair == pair) {
+            let fired = match &rule.condition {
+                AlertCondition::Threshold { level, direction } => previous_price
+                    .and_then(|previous| crossing(previous, price, *level, *direction))
+                    .map(|crossed| {
+                        (
+                            crossed.to_string(),
+                            format!("{} crossed {} {} at {}", pair, crossed, level, price),
+                        )
+                    }),
+                AlertCondition::PercentMove {
+                    percent,
+                    window_secs,
+                } => self
+                    .largest_move(pair, now, price, *window_secs)
+                    .filter(|change_pct| change_pct.abs() >= *percent)
+                    .map(|change_pct| {
+                        let direction = if change_pct > Decimal::ZERO {
+                            "up"
+                        } else {
+                            "down"
+                        };
+                        (
+                            direction.to_string(),
+                            format!(
+                                "{} moved {}% within {}s to {}",
+                                pair,
+                                change_pct.round_dp(2),
+                                window_secs,
+                                price
+                            ),
+                        )
+                    }),
+                AlertCondition::Failover => change
+                    .previous_source
+                    .as_ref()
+                    .filter(|previous| **previous != change.data.source)
+                    .map(|previous| {
+                        (
+                            format!("{}->{}", previous, change.data.source),
+                            format!(
+                                "{} failed over from {} to {}",
+                                pair, previous, change.data.source
+                            ),
+                        )
+                    }),
+                AlertCondition::Stale => {
+                    if self.stale_rules.remove(&rule.id) {
+                        info!(rule_id = %rule.id, pair = %pair, "Pair is fresh again");
+                    }
+                    None
+                }
+            };
+
+            if let Some((dedup_key, message)) = fired {
+                let event = AlertEvent {
+                    id: format!("{}-{}", rule.id, now.timestamp_millis()),
+                    rule_id: rule.id.clone(),
+                    kind: rule.kind(),
+                    pair: pair.clone(),
+                    message,
+                    price: Some(price.to_string()),
+                    source: Some(change.data.source.clone()),
+                    timestamp: now,
+                };
+                self.fire(rule, &dedup_key, event);
+            }
+        }
+    }
+
+    async fn check_staleness(&mut self) {
+        let now = Utc::now();
+        let rules = self.rules.list().await;
+
+        // Forget state for rules removed through the admin API
+        self.stale_rules
+            .retain(|id| rules.iter().any(|rule| &rule.id == id));
+
+        for rule in rules
+            .iter()
+            .filter(|r| matches!(r.condition, AlertCondition::Stale))
+        {
+            if self.state.get_price(&rule.pair).await.is_ok() {
+                self.stale_rules.remove(&rule.id);
+                continue;
+            }
+
+            // Fire once per stale episode
+            if !self.stale_rules.insert(rule.id.clone()) {
+                continue;
+            }
+
+            let event = AlertEvent {
+                id: format!("{}-{}", rule.id, now.timestamp_millis()),
+                rule_id: rule.id.clone(),
+                kind: rule.kind(),
+                pair: rule.pair.clone(),
+                message: format!("{} has no fresh price from any source", rule.pair),
+                price: None,
+                source: None,
+                timestamp: now,
+            };
+            self.fire(rule, "stale", event);
+        }
+    }
+
+    fn fire(&mut self, rule: &AlertRule, dedup_key: &str, event: AlertEvent) {
+        let key = (rule.id.clone(), dedup_key.to_string());
+
+        if let Some(last) = self.last_fired.get(&key) {
+            if event.timestamp - *last < self.dedup_window {
+                self.metrics
+                    .record_alert(&rule.id, rule.kind(), "deduplicated");
+                return;
+            }
+        }
+
+        info!(rule_id = %rule.id, pair = %event.pair, message = %event.message, "Alert fired");
+
+        self.last_fired.insert(key, event.timestamp);
+        self.metrics.record_alert(&rule.id, rule.kind(), "fired");
+        self.notifier.send(event, rule.webhook_url.clone());
+    }
+
+    fn record_history(
+        &mut self,
+        pair: &str,
+        now: DateTime<Utc>,
+        price: Decimal,
+        rules: &[AlertRule],
+    ) {
+        let max_window = rules
+            .iter()
+            .filter(|r| r.pair == pair)
+            .filter_map(|r| match r.condition {
+                AlertCondition::PercentMove { window_secs, .. } => Some(window_secs),
+                _ => None,
+            })
+            .max();
+
+        let Some(max_window) = max_window else {
+            self.history.remove(pair);
+            return;
+        };
+
+        let history = self.history.entry(pair.to_string()).or_default();
+        history.push_back((now, price));
+
+        let cutoff = now - Duration::seconds(max_window as i64);
+        while history.front().is_some_and(|(ts, _)| *ts < cutoff) {
+            history.pop_front();
+        }
+    }
+
+    /// Largest signed percent move from any price within the window to `price`
+    fn largest_move(
+        &self,
+        pair: &str,
+        now: DateTime<Utc>,
+        price: Decimal,
+        window_secs: u64,
+    ) -> Option<Decimal> {
+        let cutoff = now - Duration::seconds(window_secs as i64);
+
+        self.history
+            .get(pair)?
+            .iter()
+            .filter(|(ts, _)| *ts >= cutoff)
+            .filter_map(|(_, past)| percent_move(*past, price))
+            .max_by_key(|change_pct| change_pct.abs())
+    }
+}
+
+/// Signed percent change from `past` to `price`; `None` for a zero base or a
+/// change too large to represent
+fn percent_move(past: Decimal, price: Decimal) -> Option<Decimal> {
+    price
+        .checked_sub(past)?
+        .checked_div(past)?
+        .checked_mul(Decimal::ONE_HUNDRED)
+}
+
+/// Direction in which `level` was crossed going from `previous` to `current`, if any
+fn crossing(
+    previous: Decimal,
+    current: Decimal,
+    level: Decimal,
+    direction: CrossDirection,
+) -> Option<&'static str> {
+    let crossed_above = previous < level && current >= level;
+    let crossed_below = previous > level && current <= level;
+
+    match direction {
+        CrossDirection::Above | CrossDirection::Either if crossed_above => Some("above"),
+        CrossDirection::Below | CrossDirection::Either if crossed_below => Some("below"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decimal(value: i64) -> Decimal {
+        Decimal::from(value)
+    }
+
+    #[test]
+    fn crossing_above_and_below() {
+        let level = decimal(100);
+
+        assert_eq!(
+            crossing(decimal(99), decimal(101), level, CrossDirection::Above),
+            Some("above")
+        );
+        assert_eq!(
+            crossing(decimal(99), level, level, CrossDirection::Above),
+            Some("above")
+        );
+        assert_eq!(
+            crossing(decimal(101), decimal(99), level, CrossDirection::Below),
+            Some("below")
+        );
+        assert_eq!(
+            crossing(decimal(101), level, level, CrossDirection::Below),
+            Some("below")
+        );
+
+        // Wrong direction for the rule
+        assert_eq!(
+            crossing(decimal(99), decimal(101), level, CrossDirection::Below),
+            None
+        );
+        assert_eq!(
+            crossing(decimal(101), decimal(99), level, CrossDirection::Above),
+            None
+        );
+    }
+
+    #[test]
+    fn crossing_either_direction() {
+        let level = decimal(100);
+
+        assert_eq!(
+            crossing(decimal(99), decimal(101), level, CrossDirection::Either),
+            Some("above")
+        );
+        assert_eq!(
+            crossing(decimal(101), decimal(99), level, CrossDirection::Either),
+            Some("below")
+        );
+    }
+
+    #[test]
+    fn staying_on_one_side_is_not_a_crossing() {
+        let level = decimal(100);
+
+        for direction in [
+            CrossDirection::Above,
+            CrossDirection::Below,
+            CrossDirection::Either,
+        ] {
+            assert_eq!(crossing(decimal(101), decimal(105), level, direction), None);
+            assert_eq!(crossing(decimal(95), decimal(99), level, direction), None);
+            // Leaving the level is not a new crossing
+            assert_eq!(crossing(level, decimal(101), level, direction), None);
+            assert_eq!(crossing(level, decimal(99), level, direction), None);
+        }
+    }
+
+    #[test]
+    fn percent_move_is_signed() {
+        assert_eq!(percent_move(decimal(100), decimal(105)), Some(decimal(5)));
+        assert_eq!(percent_move(decimal(200), decimal(150)), Some(decimal(-25)));
+    }
+
+    #[test]
+    fn percent_move_skips_zero_and_overflowing_bases() {
+        assert_eq!(percent_move(Decimal::ZERO, decimal(1)), None);
+        assert_eq!(percent_move(Decimal::new(1, 28), Decimal::MAX), None);
+        assert_eq!(percent_move(Decimal::MIN, Decimal::MAX), None);
+    }
+}
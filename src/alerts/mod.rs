@@ -0,0 +1,26 @@
+mod engine;
+mod rules;
+mod webhook;
+
+pub use engine::AlertEngine;
+pub use rules::{AlertRule, AlertRules};
+pub use webhook::WebhookNotifier;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+/// Payload POSTed to webhook receivers when a rule fires
+#[derive(Debug, Clone, Serialize)]
+pub struct AlertEvent {
+    /// Stable per firing, so receivers can drop duplicate deliveries after retries
+    pub id: String,
+    pub rule_id: String,
+    pub kind: &'static str,
+    pub pair: String,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    pub timestamp: DateTime<Utc>,
+}
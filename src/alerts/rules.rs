@@ -0,0 +1,123 @@
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use crate::error::AlertError;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AlertRule {
+    pub id: String,
+    pub pair: String,
+    #[serde(flatten)]
+    pub condition: AlertCondition,
+    /// Overrides `alerts.webhook_url` for this rule
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub webhook_url: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AlertCondition {
+    /// Selected price crosses `level` in the given direction
+    Threshold {
+        level: Decimal,
+        #[serde(default)]
+        direction: CrossDirection,
+    },
+    /// Selected price moves more than `percent` within `window_secs`
+    PercentMove { percent: Decimal, window_secs: u64 },
+    /// Selected source changes from one feed to another
+    Failover,
+    /// Pair has no fresh price from any source
+    Stale,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CrossDirection {
+    Above,
+    Below,
+    #[default]
+    Either,
+}
+
+impl AlertRule {
+    pub fn validate(&self) -> Result<(), AlertError> {
+        if self.id.trim().is_empty() {
+            return Err(AlertError::InvalidRule("id must not be empty".to_string()));
+        }
+        match self.pair.split_once('/') {
+            Some((base, quote)) if !base.is_empty() && !quote.is_empty() => {}
+            _ => {
+                return Err(AlertError::InvalidRule(format!(
+                    "{}: pair must be BASE/QUOTE, got \"{}\"",
+                    self.id, self.pair
+                )))
+            }
+        }
+
+        match &self.condition {
+            AlertCondition::Threshold { level, .. } if *level <= Decimal::ZERO => Err(
+                AlertError::InvalidRule(format!("{}: level must be positive", self.id)),
+            ),
+            AlertCondition::PercentMove { percent, .. } if *percent <= Decimal::ZERO => Err(
+                AlertError::InvalidRule(format!("{}: percent must be positive", self.id)),
+            ),
+            AlertCondition::PercentMove { window_secs: 0, .. } => Err(AlertError::InvalidRule(
+                format!("{}: window_secs must be positive", self.id),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self.condition {
+            AlertCondition::Threshold { .. } => "threshold",
+            AlertCondition::PercentMove { .. } => "percent_move",
+            AlertCondition::Failover => "failover",
+            AlertCondition::Stale => "stale",
+        }
+    }
+}
+
+/// Shared, runtime-editable set of alert rules
+#[derive(Clone, Default)]
+pub struct AlertRules {
+    inner: Arc<RwLock<Vec<AlertRule>>>,
+}
+
+impl AlertRules {
+    pub async fn list(&self) -> Vec<AlertRule> {
+        self.inner.read().await.clone()
+    }
+
+    /// Add a rule with its pair normalized to the uppercase form prices are keyed by
+    pub async fn add(&self, rule: AlertRule) -> Result<AlertRule, AlertError> {
+        let rule = AlertRule {
+            pair: rule.pair.trim().to_uppercase(),
+            ..rule
+        };
+        rule.validate()?;
+
+        let mut rules = self.inner.write().await;
+        if rules.iter().any(|r| r.id == rule.id) {
+            return Err(AlertError::DuplicateRule(rule.id));
+        }
+
+        rules.push(rule.clone());
+        Ok(rule)
+    }
+
+    pub async fn remove(&self, id: &str) -> Result<(), AlertError> {
+        let mut rules = self.inner.write().await;
+        let before = rules.len();
+        rules.retain(|r| r.id != id);
+
+        if rules.len() == before {
+            return Err(AlertError::RuleNotFound(id.to_string()));
+        }
+
+        Ok(())
+    }
+}
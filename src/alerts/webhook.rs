@@ -0,0 +1,126 @@
+use hmac::{Hmac, Mac};
+use reqwest::Client;
+use sha2::Sha256;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{error, info, warn};
+
+use crate::config::AlertsConfig;
+use crate::metrics::Metrics;
+
+use super::AlertEvent;
+
+const SIGNATURE_HEADER: &str = "X-RateRelay-Signature";
+const TIMESTAMP_HEADER: &str = "X-RateRelay-Timestamp";
+const EVENT_ID_HEADER: &str = "X-RateRelay-Event-Id";
+/// Upper bound for a single wait between delivery attempts
+const MAX_RETRY_BACKOFF: Duration = Duration::from_secs(300);
+
+/// Delivers alert events as signed HTTP POSTs, retrying with exponential backoff
+#[derive(Clone)]
+pub struct WebhookNotifier {
+    client: Client,
+    default_url: Option<String>,
+    secret: Option<String>,
+    max_retries: u32,
+    retry_backoff: Duration,
+    metrics: Arc<Metrics>,
+}
+
+impl WebhookNotifier {
+    pub fn new(config: &AlertsConfig, client: Client, metrics: Arc<Metrics>) -> Self {
+        Self {
+            client,
+            default_url: config.webhook_url.clone(),
+            secret: config
+                .secret
+                .as_ref()
+                .map(|secret| secret.expose().to_string()),
+            max_retries: config.max_retries,
+            retry_backoff: Duration::from_millis(config.retry_backoff_ms),
+            metrics,
+        }
+    }
+
+    /// Deliver in the background so slow receivers never block rule evaluation
+    pub fn send(&self, event: AlertEvent, url_override: Option<String>) {
+        let Some(url) = url_override.or_else(|| self.default_url.clone()) else {
+            warn!(rule_id = %event.rule_id, "Alert fired but no webhook URL configured");
+            return;
+        };
+
+        let notifier = self.clone();
+        tokio::spawn(async move {
+            notifier.deliver(&url, &event).await;
+        });
+    }
+
+    async fn deliver(&self, url: &str, event: &AlertEvent) {
+        let body = match serde_json::to_string(event) {
+            Ok(body) => body,
+            Err(e) => {
+                error!(rule_id = %event.rule_id, error = %e, "Failed to serialize alert");
+                return;
+            }
+        };
+
+        let mut backoff = self.retry_backoff;
+
+        for attempt in 0..=self.max_retries {
+            if attempt > 0 {
+                tokio::time::sleep(backoff).await;
+                backoff = (backoff * 2).min(MAX_RETRY_BACKOFF);
+            }
+
+            let timestamp = chrono::Utc::now().timestamp().to_string();
+            let mut request = self
+                .client
+                .post(url)
+                .timeout(Duration::from_secs(5))
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .header(TIMESTAMP_HEADER, &timestamp)
+                .header(EVENT_ID_HEADER, &event.id)
+                .body(body.clone());
+
+            if let Some(ref secret) = self.secret {
+                request = request.header(SIGNATURE_HEADER, sign(secret, &timestamp, &body));
+            }
+
+            match request.send().await {
+                Ok(response) if response.status().is_success() => {
+                    info!(rule_id = %event.rule_id, attempt, "Alert delivered");
+                    self.metrics.record_webhook_delivery("success");
+                    return;
+                }
+                Ok(response) => {
+                    let status = response.status();
+                    warn!(rule_id = %event.rule_id, attempt, status = %status, "Webhook rejected alert");
+
+                    // Other client errors will not succeed on retry
+                    if status.is_client_error() && status != reqwest::StatusCode::TOO_MANY_REQUESTS
+                    {
+                        break;
+                    }
+                }
+                Err(e) => {
+                    warn!(rule_id = %event.rule_id, attempt, error = %e, "Webhook delivery failed");
+                }
+            }
+        }
+
+        error!(rule_id = %event.rule_id, url = %url, "Giving up on alert delivery");
+        self.metrics.record_webhook_delivery("failed");
+    }
+}
+
+/// `sha256=<hex HMAC-SHA256 of "<timestamp>.<body>">`, binding the body to the
+/// timestamp header so receivers can reject replays
+fn sign(secret: &str, timestamp: &str, body: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(timestamp.as_bytes());
+    mac.update(b".");
+    mac.update(body.as_bytes());
+
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
@@ -0,0 +1,127 @@
+use axum::{
+    extract::{Path, Request, State},
+    http::{header, StatusCode},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    routing::{delete, get, post},
+    Json, Router,
+};
+
+use crate::alerts::AlertRule;
+use crate::config::FeedConfig;
+use crate::error::{AlertError, RegistryError};
+
+use super::auth::{constant_time_eq, error_response};
+use super::routes::ApiState;
+
+/// Routes under `/admin`, all requiring `Authorization: Bearer <admin.token>`
+pub fn admin_router(state: ApiState) -> Router<ApiState> {
+    Router::new()
+        .route("/alerts", get(list_alerts).post(create_alert))
+        .route("/alerts/:id", delete(delete_alert))
+        .route("/feeds", post(create_feed))
+        .route("/feeds/:id", delete(delete_feed))
+        .route("/feeds/:id/pause", post(pause_feed))
+        .route("/feeds/:id/resume", post(resume_feed))
+        .route("/feeds/:id/fetch-now", post(fetch_feed_now))
+        .route_layer(middleware::from_fn_with_state(state, require_admin))
+}
+
+async fn require_admin(State(state): State<ApiState>, request: Request, next: Next) -> Response {
+    let provided = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+
+    let authorized = match (state.admin_token.as_deref(), provided) {
+        (Some(expected), Some(provided)) => constant_time_eq(expected, provided),
+        _ => false,
+    };
+
+    if !authorized {
+        return error_response(StatusCode::UNAUTHORIZED, "Invalid or missing admin token");
+    }
+
+    next.run(request).await
+}
+
+async fn list_alerts(State(state): State<ApiState>) -> impl IntoResponse {
+    Json(state.alert_rules.list().await)
+}
+
+async fn create_alert(State(state): State<ApiState>, Json(rule): Json<AlertRule>) -> Response {
+    match state.alert_rules.add(rule).await {
+        Ok(rule) => (StatusCode::CREATED, Json(rule)).into_response(),
+        Err(e) => alert_error_response(e),
+    }
+}
+
+async fn delete_alert(State(state): State<ApiState>, Path(id): Path<String>) -> Response {
+    match state.alert_rules.remove(&id).await {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => alert_error_response(e),
+    }
+}
+
+fn alert_error_response(error: AlertError) -> Response {
+    let status = match error {
+        AlertError::DuplicateRule(_) => StatusCode::CONFLICT,
+        AlertError::RuleNotFound(_) => StatusCode::NOT_FOUND,
+        AlertError::InvalidRule(_) => StatusCode::UNPROCESSABLE_ENTITY,
+    };
+
+    error_response(status, &error.to_string())
+}
+
+async fn create_feed(State(state): State<ApiState>, Json(config): Json<FeedConfig>) -> Response {
+    let id = config.id();
+
+    match state.registry.add(config).await {
+        Ok(()) => (StatusCode::CREATED, Json(state.app_state.feed(&id).await)).into_response(),
+        Err(e) => registry_error_response(e),
+    }
+}
+
+async fn delete_feed(State(state): State<ApiState>, Path(id): Path<String>) -> Response {
+    match state.registry.remove(&id).await {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => registry_error_response(e),
+    }
+}
+
+async fn pause_feed(State(state): State<ApiState>, Path(id): Path<String>) -> Response {
+    set_feed_paused(state, id, true).await
+}
+
+async fn resume_feed(State(state): State<ApiState>, Path(id): Path<String>) -> Response {
+    set_feed_paused(state, id, false).await
+}
+
+async fn set_feed_paused(state: ApiState, id: String, paused: bool) -> Response {
+    match state.registry.set_paused(&id, paused).await {
+        Ok(()) => Json(state.app_state.feed(&id).await).into_response(),
+        Err(e) => registry_error_response(e),
+    }
+}
+
+async fn fetch_feed_now(State(state): State<ApiState>, Path(id): Path<String>) -> Response {
+    match state.registry.fetch_now(&id).await {
+        Ok(price) => Json(price).into_response(),
+        Err(e) => registry_error_response(e),
+    }
+}
+
+fn registry_error_response(error: RegistryError) -> Response {
+    let status = match error {
+        RegistryError::DuplicateFeed(_) | RegistryError::FeedDisabled(_) => StatusCode::CONFLICT,
+        RegistryError::FeedNotFound(_) => StatusCode::NOT_FOUND,
+        RegistryError::InvalidFeed(_) => StatusCode::UNPROCESSABLE_ENTITY,
+        RegistryError::Fetch(_) => StatusCode::BAD_GATEWAY,
+        RegistryError::SchedulerStopped(_) | RegistryError::Persist(_) => {
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    };
+
+    error_response(status, &error.to_string())
+}
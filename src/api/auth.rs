@@ -0,0 +1,186 @@
+use axum::{
+    extract::{Query, Request, State},
+    http::{header, HeaderValue, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Instant;
+
+use crate::config::ApiKeyConfig;
+use crate::models::ErrorResponse;
+
+use super::routes::ApiState;
+
+const API_KEY_HEADER: &str = "x-api-key";
+const API_KEY_QUERY_PARAM: &str = "api_key";
+
+/// Configured API keys with a token bucket per key
+pub struct ApiKeyAuth {
+    keys: Vec<ApiKeyConfig>,
+    buckets: Mutex<HashMap<String, TokenBucket>>,
+}
+
+/// Authenticated caller, inserted into request extensions by `require_api_key`
+#[derive(Debug, Clone)]
+pub struct ApiClient {
+    /// Uppercase pairs this key may read; empty means all pairs
+    allowed_pairs: Vec<String>,
+}
+
+impl ApiClient {
+    /// Pairs match case-insensitively, whatever case the feed or path used
+    pub fn allows(&self, pair: &str) -> bool {
+        self.allowed_pairs.is_empty()
+            || self
+                .allowed_pairs
+                .iter()
+                .any(|allowed| allowed.eq_ignore_ascii_case(pair))
+    }
+}
+
+/// Whether an optional caller (auth disabled when `None`) may read a pair
+pub fn pair_allowed(client: Option<&ApiClient>, pair: &str) -> bool {
+    client.is_none_or(|client| client.allows(pair))
+}
+
+struct TokenBucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl ApiKeyAuth {
+    pub fn new(keys: Vec<ApiKeyConfig>) -> Self {
+        Self {
+            keys,
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn find(&self, provided: &str) -> Option<&ApiKeyConfig> {
+        self.keys
+            .iter()
+            .find(|key| constant_time_eq(key.key.expose(), provided))
+    }
+
+    /// Take one token from the key's bucket, or return seconds until one is available
+    fn try_acquire(&self, key: &ApiKeyConfig) -> Result<(), u64> {
+        let rate = key.requests_per_second as f64;
+        let now = Instant::now();
+
+        let mut buckets = self.buckets.lock().unwrap();
+        let bucket = buckets.entry(key.name.clone()).or_insert(TokenBucket {
+            tokens: rate,
+            last_refill: now,
+        });
+
+        let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * rate).min(rate);
+        bucket.last_refill = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(((1.0 - bucket.tokens) / rate).ceil().max(1.0) as u64)
+        }
+    }
+}
+
+/// Rejects requests without a valid key (401) or over the key's budget (429)
+pub async fn require_api_key(
+    State(state): State<ApiState>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let Some(auth) = state.api_keys.as_ref() else {
+        return next.run(request).await;
+    };
+
+    let provided = request
+        .headers()
+        .get(API_KEY_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+        .or_else(|| query_api_key(request.uri()));
+
+    let Some(key) = provided.as_deref().and_then(|provided| auth.find(provided)) else {
+        state
+            .metrics
+            .record_api_key_request("invalid", "unauthorized");
+        return error_response(StatusCode::UNAUTHORIZED, "Invalid or missing API key");
+    };
+
+    if let Err(retry_after) = auth.try_acquire(key) {
+        state
+            .metrics
+            .record_api_key_request(&key.name, "rate_limited");
+
+        let mut response = error_response(StatusCode::TOO_MANY_REQUESTS, "Rate limit exceeded");
+        response
+            .headers_mut()
+            .insert(header::RETRY_AFTER, HeaderValue::from(retry_after));
+        return response;
+    }
+
+    state.metrics.record_api_key_request(&key.name, "allowed");
+
+    request.extensions_mut().insert(ApiClient {
+        allowed_pairs: key
+            .allowed_pairs
+            .iter()
+            .map(|pair| pair.to_uppercase())
+            .collect(),
+    });
+
+    next.run(request).await
+}
+
+/// `?api_key=`, percent-decoded
+fn query_api_key(uri: &axum::http::Uri) -> Option<String> {
+    let Query(mut params) = Query::<HashMap<String, String>>::try_from_uri(uri).ok()?;
+    params.remove(API_KEY_QUERY_PARAM)
+}
+
+pub fn error_response(status: StatusCode, message: &str) -> Response {
+    (
+        status,
+        Json(ErrorResponse {
+            error: message.to_string(),
+        }),
+    )
+        .into_response()
+}
+
+/// Compare secrets without short-circuiting on the first differing byte
+pub fn constant_time_eq(a: &str, b: &str) -> bool {
+    a.len() == b.len()
+        && a.bytes()
+            .zip(b.bytes())
+            .fold(0u8, |acc, (x, y)| acc | (x ^ y))
+            == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pairs_match_case_insensitively() {
+        let client = ApiClient {
+            allowed_pairs: vec!["SOL/USDC".to_string()],
+        };
+
+        assert!(pair_allowed(Some(&client), "SOL/USDC"));
+        assert!(pair_allowed(Some(&client), "sol/usdc"));
+        assert!(!pair_allowed(Some(&client), "ETH/USDC"));
+        assert!(pair_allowed(None, "ETH/USDC"));
+
+        let unrestricted = ApiClient {
+            allowed_pairs: Vec::new(),
+        };
+        assert!(pair_allowed(Some(&unrestricted), "eth/usdc"));
+    }
+}
@@ -0,0 +1,37 @@
+use axum::{
+    extract::{MatchedPath, Request, State},
+    middleware::Next,
+    response::Response,
+};
+use std::time::Instant;
+
+use super::routes::ApiState;
+
+/// Label for requests that did not match any route, to keep cardinality bounded
+const UNMATCHED_ROUTE: &str = "unmatched";
+
+/// Records request count and latency per matched route template, method and status
+pub async fn track_http_metrics(
+    State(state): State<ApiState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| UNMATCHED_ROUTE.to_string());
+    let method = request.method().to_string();
+    let start = Instant::now();
+
+    let response = next.run(request).await;
+
+    state.metrics.record_http_request(
+        &route,
+        &method,
+        response.status().as_u16(),
+        start.elapsed().as_secs_f64(),
+    );
+
+    response
+}
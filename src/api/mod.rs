@@ -1,3 +1,7 @@
+mod admin;
+mod auth;
+mod http_metrics;
 mod routes;
 
-pub use routes::create_router;
+pub use auth::ApiKeyAuth;
+pub use routes::{create_router, ApiState};
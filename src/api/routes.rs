@@ -1,37 +1,170 @@
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
+    extract::{DefaultBodyLimit, Path, Query, State},
+    http::{header, HeaderName, HeaderValue, Method, StatusCode},
+    middleware,
     response::IntoResponse,
     routing::get,
-    Json, Router,
+    Extension, Json, Router,
 };
 use chrono::Utc;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::str::FromStr;
 use std::sync::Arc;
+use std::time::Duration;
+use tower_http::compression::CompressionLayer;
+use tower_http::cors::{AllowOrigin, CorsLayer};
+use tower_http::limit::RequestBodyLimitLayer;
+use tower_http::timeout::TimeoutLayer;
+use tracing::warn;
 
+use crate::alerts::AlertRules;
+use crate::config::HttpConfig;
+use crate::error::PriceError;
 use crate::metrics::Metrics;
-use crate::models::{ErrorResponse, HealthResponse, PriceResponse};
+use crate::models::{
+    BatchPriceResponse, BatchPriceResult, ConversionLegResponse, ConversionResponse, ErrorResponse,
+    HealthDetailsResponse, HealthResponse, PairErrorResponse, PriceResponse, PublicKeyResponse,
+    SourcePriceResponse, SourcesResponse,
+};
+use crate::registry::FeedRegistry;
+use crate::signing::PriceSigner;
 use crate::state::AppState;
 
+use super::admin::admin_router;
+use super::auth::{pair_allowed, require_api_key, ApiClient, ApiKeyAuth};
+use super::http_metrics::track_http_metrics;
+
+/// Upper bound on pairs per batch request
+const MAX_BATCH_PAIRS: usize = 100;
+
 #[derive(Clone)]
 pub struct ApiState {
     pub app_state: AppState,
     pub metrics: Arc<Metrics>,
+    pub alert_rules: AlertRules,
+    /// Live feed schedulers, managed through `/admin/feeds`
+    pub registry: FeedRegistry,
+    /// Admin routes are only mounted when a token is configured
+    pub admin_token: Option<String>,
+    /// Normalized `health.required_pairs`
+    pub required_pairs: Vec<String>,
+    /// Base-unit decimals per uppercase token symbol, for raw conversions
+    pub token_decimals: HashMap<String, u32>,
+    /// Present when `signing.key_file` is configured
+    pub signer: Option<Arc<PriceSigner>>,
+    /// Present when `api_keys` are configured; guards all `/api/v1` routes
+    pub api_keys: Option<Arc<ApiKeyAuth>>,
 }
 
-pub fn create_router(app_state: AppState, metrics: Arc<Metrics>) -> Router {
-    let api_state = ApiState { app_state, metrics };
-
-    Router::new()
-        .route("/health", get(health))
+pub fn create_router(api_state: ApiState, http: &HttpConfig) -> Router {
+    let api_routes = Router::new()
         .route("/api/v1/price/:base/:quote", get(get_price))
+        .route("/api/v1/price/:base/:quote/sources", get(get_sources))
+        .route("/api/v1/prices", get(get_prices).post(post_prices))
+        .route("/api/v1/convert", get(convert))
+        .route("/api/v1/pubkey", get(public_key))
+        .route("/api/v1/pairs", get(list_pairs))
+        .route("/api/v1/feeds", get(list_feeds))
+        .route_layer(middleware::from_fn_with_state(
+            api_state.clone(),
+            require_api_key,
+        ));
+
+    let mut router = Router::new()
+        .route("/health", get(health_ready))
+        .route("/health/live", get(health_live))
+        .route("/health/ready", get(health_ready))
+        .route("/health/details", get(health_details))
         .route("/metrics", get(metrics_handler))
-        .with_state(api_state)
+        .merge(api_routes);
+
+    if api_state.admin_token.is_some() {
+        router = router.nest("/admin", admin_router(api_state.clone()));
+    }
+
+    // Layers wrap everything added before them, so CORS ends up outermost
+    // and also decorates timeout and body-limit rejections
+    router = router
+        .layer(DefaultBodyLimit::disable())
+        .layer(RequestBodyLimitLayer::new(http.max_body_bytes))
+        .layer(TimeoutLayer::new(Duration::from_millis(
+            http.request_timeout_ms,
+        )))
+        .layer(middleware::from_fn_with_state(
+            api_state.clone(),
+            track_http_metrics,
+        ));
+
+    if http.gzip || http.brotli {
+        router = router.layer(CompressionLayer::new().gzip(http.gzip).br(http.brotli));
+    }
+
+    if let Some(cors) = cors_layer(http) {
+        router = router.layer(cors);
+    }
+
+    router.with_state(api_state)
+}
+
+fn cors_layer(http: &HttpConfig) -> Option<CorsLayer> {
+    if http.cors_allowed_origins.is_empty() {
+        return None;
+    }
+
+    let origins = if http.cors_allowed_origins.iter().any(|origin| origin == "*") {
+        AllowOrigin::any()
+    } else {
+        AllowOrigin::list(http.cors_allowed_origins.iter().filter_map(|origin| {
+            HeaderValue::from_str(origin)
+                .inspect_err(|_| warn!(origin = %origin, "Ignoring invalid CORS origin"))
+                .ok()
+        }))
+    };
+
+    let methods: Vec<Method> = http
+        .cors_allowed_methods
+        .iter()
+        .filter_map(|method| {
+            Method::from_bytes(method.to_uppercase().as_bytes())
+                .inspect_err(|_| warn!(method = %method, "Ignoring invalid CORS method"))
+                .ok()
+        })
+        .collect();
+
+    Some(
+        CorsLayer::new()
+            .allow_origin(origins)
+            .allow_methods(methods)
+            .allow_headers([
+                header::CONTENT_TYPE,
+                header::AUTHORIZATION,
+                HeaderName::from_static("x-api-key"),
+            ]),
+    )
+}
+
+/// Process is up and serving requests, regardless of price data
+async fn health_live() -> impl IntoResponse {
+    Json(HealthResponse {
+        status: "alive".to_string(),
+        timestamp: Utc::now(),
+        reason: None,
+    })
 }
 
-async fn health(State(state): State<ApiState>) -> impl IntoResponse {
-    let has_data = state.app_state.has_fresh_data().await;
+/// Every required pair can be served, possibly from a fallback source
+async fn health_ready(State(state): State<ApiState>) -> impl IntoResponse {
+    let pairs = state.app_state.pair_health(&state.required_pairs).await;
+
+    let not_ready: Vec<String> = pairs
+        .iter()
+        .filter(|pair| pair.required && !pair.is_servable())
+        .map(|pair| pair.pair.clone())
+        .collect();
 
-    if has_data {
+    if pairs.iter().any(|pair| pair.required) && not_ready.is_empty() {
         (
             StatusCode::OK,
             Json(HealthResponse {
@@ -41,34 +174,110 @@ async fn health(State(state): State<ApiState>) -> impl IntoResponse {
             }),
         )
     } else {
+        let reason = if not_ready.is_empty() {
+            "No required pairs configured".to_string()
+        } else {
+            format!("No fresh price data for {}", not_ready.join(", "))
+        };
+
         (
             StatusCode::SERVICE_UNAVAILABLE,
             Json(HealthResponse {
                 status: "unhealthy".to_string(),
                 timestamp: Utc::now(),
-                reason: Some("No fresh price data available".to_string()),
+                reason: Some(reason),
             }),
         )
     }
 }
 
+async fn health_details(State(state): State<ApiState>) -> impl IntoResponse {
+    let pairs = state.app_state.pair_health(&state.required_pairs).await;
+    let ready = pairs.iter().any(|pair| pair.required)
+        && pairs
+            .iter()
+            .all(|pair| !pair.required || pair.is_servable());
+
+    let (status_code, status) = if ready {
+        (StatusCode::OK, "healthy")
+    } else {
+        (StatusCode::SERVICE_UNAVAILABLE, "unhealthy")
+    };
+
+    (
+        status_code,
+        Json(HealthDetailsResponse {
+            status: status.to_string(),
+            timestamp: Utc::now(),
+            pairs,
+        }),
+    )
+}
+
+#[derive(Debug, Deserialize)]
+struct PriceQuery {
+    /// Force a specific source instead of the best available one
+    source: Option<String>,
+    /// Attach an ed25519 signature to the response
+    #[serde(default)]
+    signed: bool,
+}
+
 async fn get_price(
     State(state): State<ApiState>,
     Path((base, quote)): Path<(String, String)>,
+    Query(query): Query<PriceQuery>,
+    client: Option<Extension<ApiClient>>,
 ) -> impl IntoResponse {
     let pair = format!("{}/{}", base.to_uppercase(), quote.to_uppercase());
 
-    state.metrics.record_http_request(&format!("/api/v1/price/{}/{}", base, quote));
+    if !pair_allowed(client.as_deref(), &pair) {
+        return forbidden_pair(&pair);
+    }
 
-    match state.app_state.get_price(&pair).await {
-        Some((price_data, fallback_used)) => {
-            let response = PriceResponse::from_price_data(&price_data, fallback_used);
-            (StatusCode::OK, Json(serde_json::to_value(response).unwrap()))
-        }
-        None => {
+    let signer = match (query.signed, state.signer.as_ref()) {
+        (false, _) => None,
+        (true, Some(signer)) => Some(signer),
+        (true, None) => {
             let response = ErrorResponse {
-                error: format!("No price data available for {}", pair),
+                error: "Signing is not configured".to_string(),
             };
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::to_value(response).unwrap()),
+            );
+        }
+    };
+
+    let price = match query.source.as_deref() {
+        Some(source) => state
+            .app_state
+            .get_price_from_source(&pair, &source.to_lowercase())
+            .await
+            .ok_or(PriceError::Stale),
+        None => state.app_state.get_price(&pair).await,
+    };
+
+    match price {
+        Ok((price_data, fallback_used)) => {
+            let mut response = PriceResponse::from_price_data(&price_data, fallback_used);
+            if let Some(signer) = signer {
+                response = response.signed(signer);
+            }
+            (
+                StatusCode::OK,
+                Json(serde_json::to_value(response).unwrap()),
+            )
+        }
+        Err(e) => {
+            let error = match (query.source, e) {
+                (Some(source), _) => format!("No fresh price data from {} for {}", source, pair),
+                (None, PriceError::InsufficientSources) => {
+                    format!("Not enough fresh sources for {}", pair)
+                }
+                (None, _) => format!("No price data available for {}", pair),
+            };
+            let response = ErrorResponse { error };
             (
                 StatusCode::SERVICE_UNAVAILABLE,
                 Json(serde_json::to_value(response).unwrap()),
@@ -77,6 +286,299 @@ async fn get_price(
     }
 }
 
+async fn get_sources(
+    State(state): State<ApiState>,
+    Path((base, quote)): Path<(String, String)>,
+    client: Option<Extension<ApiClient>>,
+) -> impl IntoResponse {
+    let pair = format!("{}/{}", base.to_uppercase(), quote.to_uppercase());
+
+    if !pair_allowed(client.as_deref(), &pair) {
+        return forbidden_pair(&pair);
+    }
+    let sources = state.app_state.get_sources(&pair).await;
+
+    if sources.is_empty() {
+        let response = ErrorResponse {
+            error: format!("No sources have reported a price for {}", pair),
+        };
+        return (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::to_value(response).unwrap()),
+        );
+    }
+
+    let now = Utc::now();
+    let response = SourcesResponse {
+        pair,
+        sources: sources
+            .iter()
+            .map(|source| SourcePriceResponse::from_source_price(source, now))
+            .collect(),
+    };
+
+    (
+        StatusCode::OK,
+        Json(serde_json::to_value(response).unwrap()),
+    )
+}
+
+#[derive(Debug, Deserialize)]
+struct BatchQuery {
+    /// Comma-separated list, e.g. `SOL/USDC,BTC/USDC`
+    pairs: String,
+}
+
+async fn get_prices(
+    State(state): State<ApiState>,
+    Query(query): Query<BatchQuery>,
+    client: Option<Extension<ApiClient>>,
+) -> impl IntoResponse {
+    let pairs: Vec<String> = query
+        .pairs
+        .split(',')
+        .map(str::trim)
+        .filter(|pair| !pair.is_empty())
+        .map(str::to_string)
+        .collect();
+
+    batch_prices(&state, pairs, client.as_deref()).await
+}
+
+async fn post_prices(
+    State(state): State<ApiState>,
+    client: Option<Extension<ApiClient>>,
+    Json(pairs): Json<Vec<String>>,
+) -> impl IntoResponse {
+    batch_prices(&state, pairs, client.as_deref()).await
+}
+
+async fn batch_prices(
+    state: &ApiState,
+    pairs: Vec<String>,
+    client: Option<&ApiClient>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    if pairs.is_empty() || pairs.len() > MAX_BATCH_PAIRS {
+        let response = ErrorResponse {
+            error: format!("Expected between 1 and {} pairs", MAX_BATCH_PAIRS),
+        };
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::to_value(response).unwrap()),
+        );
+    }
+
+    let normalized: Vec<Result<String, PriceError>> = pairs
+        .iter()
+        .map(|p| match normalize_pair(p) {
+            Some(pair) if pair_allowed(client, &pair) => Ok(pair),
+            Some(_) => Err(PriceError::Forbidden),
+            None => Err(PriceError::InvalidPair),
+        })
+        .collect();
+    let lookups: Vec<String> = normalized.iter().flatten().cloned().collect();
+    let mut results = state.app_state.get_prices(&lookups).await.into_iter();
+
+    let prices = pairs
+        .iter()
+        .zip(normalized)
+        .map(|(requested, pair)| {
+            let result = match pair {
+                Ok(_) => results.next().expect("one result per looked up pair"),
+                Err(e) => Err(e),
+            };
+
+            match result {
+                Ok((price_data, fallback_used)) => BatchPriceResult::Price(
+                    PriceResponse::from_price_data(&price_data, fallback_used),
+                ),
+                Err(e) => BatchPriceResult::Error(PairErrorResponse {
+                    pair: requested.clone(),
+                    error: e.to_string(),
+                    code: e.code().to_string(),
+                }),
+            }
+        })
+        .collect();
+
+    let response = BatchPriceResponse {
+        prices,
+        timestamp: Utc::now(),
+    };
+
+    (
+        StatusCode::OK,
+        Json(serde_json::to_value(response).unwrap()),
+    )
+}
+
+/// "sol/usdc" -> "SOL/USDC"; `None` unless exactly one non-empty base and quote
+fn normalize_pair(pair: &str) -> Option<String> {
+    let (base, quote) = pair.trim().split_once('/')?;
+
+    if base.is_empty() || quote.is_empty() || quote.contains('/') {
+        return None;
+    }
+
+    Some(format!("{}/{}", base.to_uppercase(), quote.to_uppercase()))
+}
+
+#[derive(Debug, Deserialize)]
+struct ConvertQuery {
+    from: String,
+    to: String,
+    amount: String,
+    /// Interpret and return amounts in on-chain base units
+    #[serde(default)]
+    raw: bool,
+}
+
+async fn convert(
+    State(state): State<ApiState>,
+    Query(query): Query<ConvertQuery>,
+    client: Option<Extension<ApiClient>>,
+) -> impl IntoResponse {
+    let from = query.from.trim().to_uppercase();
+    let to = query.to.trim().to_uppercase();
+
+    let bad_request = |error: String| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::to_value(ErrorResponse { error }).unwrap()),
+        )
+    };
+
+    let amount = match Decimal::from_str(query.amount.trim()) {
+        Ok(amount) if amount >= Decimal::ZERO => amount,
+        _ => return bad_request(format!("Invalid amount: {}", query.amount)),
+    };
+
+    let decimals = if query.raw {
+        match (
+            state.token_decimals.get(&from),
+            state.token_decimals.get(&to),
+        ) {
+            (Some(from_decimals), Some(to_decimals)) => Some((*from_decimals, *to_decimals)),
+            (None, _) => return bad_request(format!("No decimals configured for {}", from)),
+            (_, None) => return bad_request(format!("No decimals configured for {}", to)),
+        }
+    } else {
+        None
+    };
+
+    let Some(path) = state.app_state.get_conversion_path(&from, &to).await else {
+        let response = ErrorResponse {
+            error: format!("No price path from {} to {}", from, to),
+        };
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(serde_json::to_value(response).unwrap()),
+        );
+    };
+
+    if let Some(leg) = path
+        .iter()
+        .find(|leg| !pair_allowed(client.as_deref(), &leg.data.pair))
+    {
+        return forbidden_pair(&leg.data.pair);
+    }
+
+    let rate = path
+        .iter()
+        .try_fold(Decimal::ONE, |rate, leg| rate.checked_mul(leg.rate()?));
+
+    let result = rate.and_then(|rate| match decimals {
+        // Scale base units to whole tokens, convert, then back to whole base units
+        Some((from_decimals, to_decimals)) => amount
+            .checked_mul(Decimal::try_from_i128_with_scale(1, from_decimals).ok()?)?
+            .checked_mul(rate)?
+            .checked_mul(
+                Decimal::try_from_i128_with_scale(10i128.checked_pow(to_decimals)?, 0).ok()?,
+            )
+            .map(|units| units.trunc()),
+        None => amount.checked_mul(rate),
+    });
+
+    let (Some(rate), Some(result)) = (rate, result) else {
+        return bad_request("Conversion overflowed decimal precision".to_string());
+    };
+
+    let response = ConversionResponse {
+        from,
+        to,
+        amount: amount.normalize().to_string(),
+        result: result.normalize().to_string(),
+        rate: rate.normalize().to_string(),
+        raw: query.raw,
+        path: path.iter().map(ConversionLegResponse::from_leg).collect(),
+        timestamp: path
+            .iter()
+            .map(|leg| leg.data.timestamp)
+            .min()
+            .unwrap_or_else(Utc::now),
+    };
+
+    (
+        StatusCode::OK,
+        Json(serde_json::to_value(response).unwrap()),
+    )
+}
+
+async fn public_key(State(state): State<ApiState>) -> impl IntoResponse {
+    match state.signer.as_ref() {
+        Some(signer) => (
+            StatusCode::OK,
+            Json(
+                serde_json::to_value(PublicKeyResponse {
+                    algorithm: "ed25519".to_string(),
+                    public_key: signer.public_key_hex(),
+                })
+                .unwrap(),
+            ),
+        ),
+        None => (
+            StatusCode::NOT_FOUND,
+            Json(
+                serde_json::to_value(ErrorResponse {
+                    error: "Signing is not configured".to_string(),
+                })
+                .unwrap(),
+            ),
+        ),
+    }
+}
+
+async fn list_pairs(
+    State(state): State<ApiState>,
+    client: Option<Extension<ApiClient>>,
+) -> impl IntoResponse {
+    let mut pairs = state.app_state.pairs().await;
+    pairs.retain(|info| pair_allowed(client.as_deref(), &info.pair));
+
+    Json(pairs)
+}
+
+async fn list_feeds(
+    State(state): State<ApiState>,
+    client: Option<Extension<ApiClient>>,
+) -> impl IntoResponse {
+    let mut feeds = state.app_state.feeds().await;
+    feeds.retain(|feed| pair_allowed(client.as_deref(), &feed.pair));
+
+    Json(feeds)
+}
+
+fn forbidden_pair(pair: &str) -> (StatusCode, Json<serde_json::Value>) {
+    let response = ErrorResponse {
+        error: format!("API key is not allowed to read {}", pair),
+    };
+
+    (
+        StatusCode::FORBIDDEN,
+        Json(serde_json::to_value(response).unwrap()),
+    )
+}
+
 async fn metrics_handler(State(state): State<ApiState>) -> impl IntoResponse {
     state.metrics.encode()
 }
@@ -0,0 +1,115 @@
+use clap::{Parser, Subcommand};
+use std::path::{Path, PathBuf};
+
+use crate::config::{Config, ConfigIssue, FeedConfig};
+use crate::error::ConfigError;
+use crate::feeds::create_feed;
+use crate::signing::PriceSigner;
+
+#[derive(Parser)]
+#[command(name = "rate-relay", version, about = "Aggregated token price relay")]
+pub struct Cli {
+    /// Configuration file
+    #[arg(long, short, global = true, default_value = "config.toml")]
+    pub config: PathBuf,
+
+    /// Defaults to `serve`
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Run the feed schedulers and the HTTP server
+    Serve,
+    /// Parse and validate the configuration, reporting every problem
+    CheckConfig,
+    /// Fetch a single price from one feed and print it
+    Fetch {
+        /// Configured feed id, or a feed type such as `jupiter`
+        #[arg(long)]
+        feed: String,
+        /// Pair as BASE/QUOTE, e.g. SOL/USDC
+        #[arg(long)]
+        pair: String,
+    },
+    /// Print the effective configuration with all defaults filled in
+    DumpConfig,
+}
+
+pub fn check_config(path: &Path) -> anyhow::Result<()> {
+    let config = match Config::load(path) {
+        Ok(config) => config,
+        Err(ConfigError::Invalid { issues, .. }) => return report(path, &issues),
+        Err(e) => return Err(e.into()),
+    };
+    let content = std::fs::read_to_string(path)?;
+    let mut issues = Vec::new();
+
+    // Constructing each feed catches feed-specific settings and missing secrets
+    let http_client = reqwest::Client::new();
+    for (index, feed) in config.feeds.iter().enumerate() {
+        if let Err(e) = create_feed(feed, http_client.clone()) {
+            issues.push(ConfigIssue::new(
+                vec!["feeds".into(), index.into()],
+                e.to_string(),
+            ));
+        }
+    }
+
+    if let Some(signing) = &config.signing {
+        if let Err(e) = PriceSigner::from_file(&signing.key_file) {
+            issues.push(ConfigIssue::new(
+                vec!["signing".into(), "key_file".into()],
+                e.to_string(),
+            ));
+        }
+    }
+
+    let issues: Vec<ConfigIssue> = issues
+        .into_iter()
+        .map(|issue| issue.located(&content))
+        .collect();
+    report(path, &issues)
+}
+
+fn report(path: &Path, issues: &[ConfigIssue]) -> anyhow::Result<()> {
+    if issues.is_empty() {
+        println!("{}: OK", path.display());
+        return Ok(());
+    }
+
+    for issue in issues {
+        println!("{}: {}", path.display(), issue);
+    }
+    anyhow::bail!("{} problem(s) found", issues.len())
+}
+
+/// Uses the matching `[[feeds]]` entry when the config has one, so feed-specific
+/// settings apply; otherwise `feed` is treated as a feed type with defaults
+pub async fn fetch(path: &Path, feed: &str, pair: &str) -> anyhow::Result<()> {
+    let (base, quote) = pair
+        .split_once('/')
+        .filter(|(base, quote)| !base.is_empty() && !quote.is_empty())
+        .ok_or_else(|| anyhow::anyhow!("Invalid pair {}, expected BASE/QUOTE", pair))?;
+
+    let configured = Config::load(path).ok().and_then(|config| {
+        config.feeds.into_iter().find(|config| {
+            config.id() == feed
+                || (config.feed_type == feed && config.pair().eq_ignore_ascii_case(pair))
+        })
+    });
+    let feed_config = configured.unwrap_or_else(|| FeedConfig::new(feed, base, quote));
+
+    let price_feed = create_feed(&feed_config, reqwest::Client::new())?;
+    let price = price_feed.fetch_price().await?;
+
+    println!("{}", serde_json::to_string_pretty(&price)?);
+    Ok(())
+}
+
+pub fn dump_config(path: &Path) -> anyhow::Result<()> {
+    let config = Config::load(path)?;
+    print!("{}", toml::to_string_pretty(&config)?);
+    Ok(())
+}
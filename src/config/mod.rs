@@ -0,0 +1,789 @@
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize, Serializer};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use crate::alerts::AlertRule;
+use crate::error::{ConfigError, FeedError};
+use crate::models::AggregationMode;
+
+mod persist;
+mod validate;
+
+pub use persist::{save_feed_change, FeedChange};
+pub use validate::{ConfigIssue, KeySegment};
+
+/// Variables named `RATE_RELAY__<SECTION>__<KEY>` override config file values
+const ENV_PREFIX: &str = "RATE_RELAY__";
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    pub server: ServerConfig,
+    #[serde(default)]
+    pub feeds: Vec<FeedConfig>,
+    /// Per-pair freshness, sanity bounds and aggregation
+    #[serde(default)]
+    pub pairs: Vec<PairConfig>,
+    /// Per-token settings keyed by symbol, e.g. `[tokens.SOL]`
+    #[serde(default)]
+    pub tokens: HashMap<String, TokenConfig>,
+    #[serde(default)]
+    pub sinks: SinksConfig,
+    #[serde(default)]
+    pub health: HealthConfig,
+    #[serde(default)]
+    pub alerts: AlertsConfig,
+    pub admin: Option<AdminConfig>,
+    pub signing: Option<SigningConfig>,
+    /// When non-empty, `/api/v1` routes require one of these keys
+    #[serde(default)]
+    pub api_keys: Vec<ApiKeyConfig>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ServerConfig {
+    #[serde(default = "default_port")]
+    pub port: u16,
+    #[serde(default = "default_staleness_threshold_secs")]
+    pub staleness_threshold_secs: u64,
+    #[serde(default)]
+    pub http: HttpConfig,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct HttpConfig {
+    /// Origins allowed to call the API from a browser; `"*"` allows any. Empty disables CORS.
+    #[serde(default)]
+    pub cors_allowed_origins: Vec<String>,
+    #[serde(default = "default_cors_allowed_methods")]
+    pub cors_allowed_methods: Vec<String>,
+    #[serde(default = "default_enabled")]
+    pub gzip: bool,
+    #[serde(default = "default_enabled")]
+    pub brotli: bool,
+    #[serde(default = "default_max_body_bytes")]
+    pub max_body_bytes: usize,
+    #[serde(default = "default_request_timeout_ms")]
+    pub request_timeout_ms: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct FeedConfig {
+    /// Unique instance id; defaults to `<type>-<base>-<quote>` in lowercase
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(rename = "type")]
+    pub feed_type: String,
+    pub base_token: String,
+    pub quote_token: String,
+    #[serde(default = "default_interval_ms")]
+    pub interval_ms: u64,
+    #[serde(default = "default_priority")]
+    pub priority: u32,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Environment variable holding this feed's API key
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_key_env: Option<String>,
+    /// File holding this feed's API key, e.g. a mounted `/run/secrets/...`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_key_file: Option<String>,
+    /// Log and skip the feed when it cannot be constructed instead of failing startup
+    #[serde(default)]
+    pub optional: bool,
+    /// Settings for `type = "http_json"`, as `[feeds.http_json]`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub http_json: Option<HttpJsonFeedConfig>,
+    /// Optional settings for `type = "coinbase"`, as `[feeds.coinbase]`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coinbase: Option<CoinbaseFeedConfig>,
+    /// Optional settings for `type = "kraken"`, as `[feeds.kraken]`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kraken: Option<KrakenFeedConfig>,
+    /// Optional settings for `type = "okx"`, as `[feeds.okx]`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub okx: Option<OkxFeedConfig>,
+    /// Optional settings for `type = "bybit"`, as `[feeds.bybit]`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bybit: Option<BybitFeedConfig>,
+    /// Settings for `type = "solana_pool"`, as `[feeds.solana_pool]`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub solana_pool: Option<SolanaPoolFeedConfig>,
+    /// Settings for `type = "jupiter_quote"`, as `[feeds.jupiter_quote]`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jupiter_quote: Option<JupiterQuoteFeedConfig>,
+    /// Settings for `type = "chainlink"`, as `[feeds.chainlink]`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chainlink: Option<ChainlinkFeedConfig>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct HttpJsonFeedConfig {
+    /// Request URL; `{base}`, `{quote}`, `{base_lower}` and `{quote_lower}` are substituted
+    pub url: String,
+    /// Extra request headers; `{api_key}` in a value is replaced with the feed's API key
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    /// RFC 6901 JSON pointer to the price, a number or numeric string (e.g. `/data/price`)
+    pub price_pointer: String,
+    /// JSON pointer to the quote time; the fetch time is used when unset
+    pub timestamp_pointer: Option<String>,
+    #[serde(default)]
+    pub timestamp_format: TimestampFormat,
+    /// Multiplier applied to the raw value, e.g. `0.000001` for micro-units
+    pub scale: Option<Decimal>,
+    /// Serve `1 / price`, for sources that quote the inverse pair
+    #[serde(default)]
+    pub invert: bool,
+    #[serde(default = "default_feed_timeout_ms")]
+    pub timeout_ms: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct CoinbaseFeedConfig {
+    /// Defaults to `<BASE>-<QUOTE>`, e.g. `SOL-USD`
+    pub product_id: Option<String>,
+    #[serde(default)]
+    pub mode: FeedMode,
+    #[serde(default = "default_coinbase_rest_url")]
+    pub rest_url: String,
+    #[serde(default = "default_coinbase_ws_url")]
+    pub ws_url: String,
+    #[serde(default = "default_feed_timeout_ms")]
+    pub timeout_ms: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct KrakenFeedConfig {
+    /// Kraken pair name; derived from the tokens when unset (BTC becomes XBT, e.g. `XBTUSD`)
+    pub pair: Option<String>,
+    #[serde(default = "default_kraken_rest_url")]
+    pub rest_url: String,
+    #[serde(default = "default_feed_timeout_ms")]
+    pub timeout_ms: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct OkxFeedConfig {
+    /// Defaults to `<BASE>-<QUOTE>`, e.g. `SOL-USDT`
+    pub inst_id: Option<String>,
+    #[serde(default = "default_okx_rest_url")]
+    pub rest_url: String,
+    #[serde(default = "default_feed_timeout_ms")]
+    pub timeout_ms: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct BybitFeedConfig {
+    /// Defaults to `<BASE><QUOTE>`, e.g. `SOLUSDT`
+    pub symbol: Option<String>,
+    #[serde(default = "default_bybit_rest_url")]
+    pub rest_url: String,
+    #[serde(default = "default_feed_timeout_ms")]
+    pub timeout_ms: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct SolanaPoolFeedConfig {
+    /// Base58 address of the pool account
+    pub pool: String,
+    pub kind: PoolKind,
+    #[serde(default = "default_solana_rpc_url")]
+    pub rpc_url: String,
+    /// The pool prices token A (token 0) in token B (token 1); set when the
+    /// feed's base token is B
+    #[serde(default)]
+    pub invert: bool,
+    #[serde(default = "default_feed_timeout_ms")]
+    pub timeout_ms: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PoolKind {
+    /// Constant-product pool; price from the vault reserves
+    RaydiumCpmm,
+    /// Concentrated-liquidity pool; price from `sqrt_price`
+    OrcaWhirlpool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct JupiterQuoteFeedConfig {
+    /// Trade size in whole units of the input token, e.g. `50000` USDC
+    pub amount: Decimal,
+    #[serde(default)]
+    pub side: QuoteSide,
+    /// Required for tokens other than SOL, USDC and USDT
+    pub base_decimals: Option<u8>,
+    pub quote_decimals: Option<u8>,
+    #[serde(default = "default_jupiter_quote_url")]
+    pub api_url: String,
+    #[serde(default = "default_feed_timeout_ms")]
+    pub timeout_ms: u64,
+}
+
+/// Direction of the quoted swap; `amount` is in the token being sold
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum QuoteSide {
+    /// Swap base into quote
+    #[default]
+    Sell,
+    /// Swap quote into base
+    Buy,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ChainlinkFeedConfig {
+    /// EVM JSON-RPC endpoint
+    pub rpc_url: String,
+    /// Aggregator (or proxy) contract address, `0x`-prefixed
+    pub aggregator: String,
+    /// Rounds updated longer ago are rejected; match the feed's heartbeat.
+    /// The pair's own staleness threshold must allow the same age.
+    #[serde(default = "default_chainlink_staleness_threshold_secs")]
+    pub staleness_threshold_secs: u64,
+    #[serde(default = "default_feed_timeout_ms")]
+    pub timeout_ms: u64,
+}
+
+/// How an exchange feed gets its prices
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FeedMode {
+    /// Poll the REST ticker every `interval_ms`
+    #[default]
+    Rest,
+    /// Keep a WebSocket ticker subscription open and serve its latest tick
+    Websocket,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TimestampFormat {
+    /// Seconds since the epoch, fractions allowed
+    #[default]
+    UnixSeconds,
+    UnixMillis,
+    Rfc3339,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct PairConfig {
+    /// BASE/QUOTE, matched case-insensitively
+    pub pair: String,
+    /// Overrides `server.staleness_threshold_secs` for this pair
+    pub staleness_threshold_secs: Option<u64>,
+    /// Ticks below this price are rejected
+    pub min_price: Option<Decimal>,
+    /// Ticks above this price are rejected
+    pub max_price: Option<Decimal>,
+    /// Ticks moving more than this percentage from the same source's previous
+    /// fresh price are rejected
+    pub max_move_percent: Option<Decimal>,
+    /// Fresh sources needed before the pair is served
+    #[serde(default = "default_min_sources")]
+    pub min_sources: usize,
+    #[serde(default)]
+    pub aggregation: AggregationMode,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct TokenConfig {
+    /// Number of decimals of the on-chain base unit (e.g. 9 for SOL lamports)
+    pub decimals: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct HealthConfig {
+    /// Pairs that must be fresh for `/health/ready`; empty means every pair with an enabled feed
+    #[serde(default)]
+    pub required_pairs: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct SinksConfig {
+    pub nats: Option<NatsSinkConfig>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct NatsSinkConfig {
+    #[serde(default = "default_nats_url")]
+    pub url: String,
+    #[serde(default = "default_nats_subject_prefix")]
+    pub subject_prefix: String,
+    /// Publish through JetStream so messages are persisted in `stream`
+    #[serde(default)]
+    pub jetstream: bool,
+    #[serde(default = "default_nats_stream")]
+    pub stream: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct AlertsConfig {
+    /// Default receiver for rules without their own `webhook_url`
+    pub webhook_url: Option<String>,
+    /// HMAC-SHA256 key used to sign webhook bodies
+    pub secret: Option<Secret>,
+    #[serde(default = "default_alert_max_retries")]
+    pub max_retries: u32,
+    #[serde(default = "default_alert_retry_backoff_ms")]
+    pub retry_backoff_ms: u64,
+    /// Identical alerts from the same rule within this window are suppressed
+    #[serde(default = "default_alert_dedup_window_secs")]
+    pub dedup_window_secs: u64,
+    /// How often staleness rules are evaluated
+    #[serde(default = "default_alert_check_interval_secs")]
+    pub check_interval_secs: u64,
+    #[serde(default)]
+    pub rules: Vec<AlertRule>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct AdminConfig {
+    /// Bearer token required on all `/admin` routes
+    pub token: Secret,
+    /// Write feeds added or removed through `/admin/feeds` back to the config file
+    #[serde(default)]
+    pub persist_feeds: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ApiKeyConfig {
+    /// Label used in logs and metrics instead of the key itself
+    pub name: String,
+    pub key: Secret,
+    /// Pairs this key may read; empty means all pairs
+    #[serde(default)]
+    pub allowed_pairs: Vec<String>,
+    #[serde(default = "default_requests_per_second")]
+    pub requests_per_second: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct SigningConfig {
+    /// File holding the ed25519 secret seed (32 raw bytes or 64 hex chars)
+    pub key_file: String,
+}
+
+fn default_port() -> u16 {
+    8080
+}
+
+fn default_staleness_threshold_secs() -> u64 {
+    30
+}
+
+fn default_cors_allowed_methods() -> Vec<String> {
+    vec!["GET".to_string(), "POST".to_string()]
+}
+
+fn default_max_body_bytes() -> usize {
+    64 * 1024
+}
+
+fn default_request_timeout_ms() -> u64 {
+    10_000
+}
+
+fn default_interval_ms() -> u64 {
+    1500
+}
+
+fn default_priority() -> u32 {
+    100
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_feed_timeout_ms() -> u64 {
+    5000
+}
+
+fn default_coinbase_rest_url() -> String {
+    "https://api.exchange.coinbase.com".to_string()
+}
+
+fn default_coinbase_ws_url() -> String {
+    "wss://ws-feed.exchange.coinbase.com".to_string()
+}
+
+fn default_kraken_rest_url() -> String {
+    "https://api.kraken.com".to_string()
+}
+
+fn default_okx_rest_url() -> String {
+    "https://www.okx.com".to_string()
+}
+
+fn default_bybit_rest_url() -> String {
+    "https://api.bybit.com".to_string()
+}
+
+fn default_solana_rpc_url() -> String {
+    "https://api.mainnet-beta.solana.com".to_string()
+}
+
+fn default_jupiter_quote_url() -> String {
+    "https://api.jup.ag/swap/v1".to_string()
+}
+
+fn default_chainlink_staleness_threshold_secs() -> u64 {
+    3600
+}
+
+fn default_min_sources() -> usize {
+    1
+}
+
+fn default_alert_max_retries() -> u32 {
+    3
+}
+
+fn default_alert_retry_backoff_ms() -> u64 {
+    500
+}
+
+fn default_alert_dedup_window_secs() -> u64 {
+    300
+}
+
+fn default_alert_check_interval_secs() -> u64 {
+    5
+}
+
+fn default_requests_per_second() -> u32 {
+    10
+}
+
+fn default_nats_url() -> String {
+    "nats://127.0.0.1:4222".to_string()
+}
+
+fn default_nats_subject_prefix() -> String {
+    "prices".to_string()
+}
+
+fn default_nats_stream() -> String {
+    "PRICES".to_string()
+}
+
+impl FeedConfig {
+    /// A feed with default interval, priority and id
+    pub fn new(feed_type: &str, base_token: &str, quote_token: &str) -> Self {
+        Self {
+            id: None,
+            feed_type: feed_type.to_string(),
+            base_token: base_token.to_string(),
+            quote_token: quote_token.to_string(),
+            interval_ms: default_interval_ms(),
+            priority: default_priority(),
+            enabled: default_enabled(),
+            api_key_env: None,
+            api_key_file: None,
+            optional: false,
+            http_json: None,
+            coinbase: None,
+            kraken: None,
+            okx: None,
+            bybit: None,
+            solana_pool: None,
+            jupiter_quote: None,
+            chainlink: None,
+        }
+    }
+
+    /// Resolve `api_key_env` or `api_key_file`; a referenced but missing secret is an error
+    pub fn api_key(&self) -> Result<Option<Secret>, FeedError> {
+        match (&self.api_key_env, &self.api_key_file) {
+            (Some(_), Some(_)) => Err(FeedError::Config(
+                "set only one of api_key_env and api_key_file".to_string(),
+            )),
+            (Some(name), None) => std::env::var(name)
+                .map(|key| Some(Secret::new(key)))
+                .map_err(|_| {
+                    FeedError::Config(format!("environment variable {} is not set", name))
+                }),
+            (None, Some(path)) => std::fs::read_to_string(path)
+                .map(|key| Some(Secret::new(key.trim().to_string())))
+                .map_err(|e| FeedError::Config(format!("cannot read {}: {}", path, e))),
+            (None, None) => Ok(None),
+        }
+    }
+
+    pub fn pair(&self) -> String {
+        format!("{}/{}", self.base_token, self.quote_token)
+    }
+
+    pub fn id(&self) -> String {
+        self.id.clone().unwrap_or_else(|| {
+            format!(
+                "{}-{}-{}",
+                self.feed_type, self.base_token, self.quote_token
+            )
+            .to_lowercase()
+        })
+    }
+
+    /// The `source` this feed's prices are stored under, i.e. its `PriceFeed::name()`.
+    /// Two feeds with the same source and pair would overwrite each other's price.
+    pub fn source(&self) -> String {
+        match (self.feed_type.as_str(), &self.solana_pool) {
+            ("http_json", _) => self.id(),
+            ("solana_pool", Some(settings)) => match settings.kind {
+                PoolKind::RaydiumCpmm => "raydium_cpmm".to_string(),
+                PoolKind::OrcaWhirlpool => "orca_whirlpool".to_string(),
+            },
+            (feed_type, _) => feed_type.to_string(),
+        }
+    }
+}
+
+impl Config {
+    /// Read the file, apply environment overrides, then deserialize and validate,
+    /// reporting every problem with its location in the file
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, ConfigError> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path).map_err(|source| ConfigError::Read {
+            path: path.display().to_string(),
+            source,
+        })?;
+        let invalid = |issues: Vec<ConfigIssue>| ConfigError::Invalid {
+            path: path.display().to_string(),
+            issues,
+        };
+
+        let table: toml::Table = toml::from_str(&content)
+            .map_err(|e| invalid(vec![ConfigIssue::syntax(&e, &content)]))?;
+        let mut document = toml::Value::Table(table);
+        let overrides =
+            apply_env_overrides(&mut document, std::env::vars()).map_err(ConfigError::Override)?;
+
+        validate::parse_document(document, &overrides).map_err(|issues| {
+            let mut issues: Vec<ConfigIssue> = issues
+                .into_iter()
+                .map(|issue| issue.located(&content))
+                .collect();
+            // File order; issues without a location (e.g. missing sections) go last
+            issues.sort_by_key(|issue| issue.location.unwrap_or((usize::MAX, 0)));
+            invalid(issues)
+        })
+    }
+}
+
+/// A value set from the environment rather than the file
+#[derive(Debug)]
+pub(crate) struct EnvOverride {
+    /// Variable name, e.g. `RATE_RELAY__SERVER__PORT`
+    pub name: String,
+    /// Resolved key path in the document
+    pub key: Vec<KeySegment>,
+    /// The variable's value as given
+    pub raw: String,
+}
+
+/// Apply `RATE_RELAY__SERVER__PORT=9090`-style overrides. Path segments match
+/// existing keys case-insensitively and index into arrays (`RATE_RELAY__FEEDS__0__ENABLED`).
+/// Values are parsed as TOML and fall back to a plain string; replacing a string
+/// keeps the value as given, so `RATE_RELAY__ADMIN__TOKEN=123456` stays a string.
+fn apply_env_overrides(
+    document: &mut toml::Value,
+    vars: impl Iterator<Item = (String, String)>,
+) -> Result<Vec<EnvOverride>, String> {
+    let mut overrides = Vec::new();
+    for (name, raw) in vars {
+        let Some(path) = name.strip_prefix(ENV_PREFIX) else {
+            continue;
+        };
+        let segments: Vec<&str> = path.split("__").collect();
+        if segments.iter().any(|segment| segment.is_empty()) {
+            return Err(format!("{}: empty key segment", name));
+        }
+
+        let mut key = Vec::new();
+        set_override(document, &segments, &raw, &mut key)
+            .map_err(|e| format!("{}: {}", name, e))?;
+        overrides.push(EnvOverride { name, key, raw });
+    }
+
+    Ok(overrides)
+}
+
+/// `raw` as a TOML value, falling back to a plain string
+fn override_value(raw: &str) -> toml::Value {
+    toml::from_str::<toml::Table>(&format!("value = {}", raw))
+        .ok()
+        .and_then(|mut parsed| parsed.remove("value"))
+        .unwrap_or_else(|| toml::Value::String(raw.to_string()))
+}
+
+fn set_override(
+    target: &mut toml::Value,
+    segments: &[&str],
+    raw: &str,
+    key: &mut Vec<KeySegment>,
+) -> Result<(), String> {
+    let Some((segment, rest)) = segments.split_first() else {
+        *target = match target {
+            toml::Value::String(_) => toml::Value::String(raw.to_string()),
+            _ => override_value(raw),
+        };
+        return Ok(());
+    };
+
+    match target {
+        toml::Value::Table(table) => {
+            let name = table
+                .keys()
+                .find(|key| key.eq_ignore_ascii_case(segment))
+                .cloned()
+                .unwrap_or_else(|| segment.to_lowercase());
+            key.push(KeySegment::Key(name.clone()));
+            let next = table
+                .entry(name)
+                .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+            set_override(next, rest, raw, key)
+        }
+        toml::Value::Array(items) => {
+            let (index, item) = segment
+                .parse::<usize>()
+                .ok()
+                .and_then(|index| Some((index, items.get_mut(index)?)))
+                .ok_or_else(|| format!("{} is not an index into the array", segment))?;
+            key.push(KeySegment::Index(index));
+            set_override(item, rest, raw, key)
+        }
+        _ => Err(format!("cannot set {} inside a plain value", segment)),
+    }
+}
+
+/// A configured credential; never printed by `Debug` or serialized in config dumps
+#[derive(Clone, PartialEq, Deserialize)]
+#[serde(transparent)]
+pub struct Secret(String);
+
+impl Secret {
+    pub fn new(value: String) -> Self {
+        Self(value)
+    }
+
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Debug for Secret {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("[redacted]")
+    }
+}
+
+impl Serialize for Secret {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str("[redacted]")
+    }
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            port: default_port(),
+            staleness_threshold_secs: default_staleness_threshold_secs(),
+            http: HttpConfig::default(),
+        }
+    }
+}
+
+impl Default for CoinbaseFeedConfig {
+    fn default() -> Self {
+        Self {
+            product_id: None,
+            mode: FeedMode::default(),
+            rest_url: default_coinbase_rest_url(),
+            ws_url: default_coinbase_ws_url(),
+            timeout_ms: default_feed_timeout_ms(),
+        }
+    }
+}
+
+impl Default for KrakenFeedConfig {
+    fn default() -> Self {
+        Self {
+            pair: None,
+            rest_url: default_kraken_rest_url(),
+            timeout_ms: default_feed_timeout_ms(),
+        }
+    }
+}
+
+impl Default for OkxFeedConfig {
+    fn default() -> Self {
+        Self {
+            inst_id: None,
+            rest_url: default_okx_rest_url(),
+            timeout_ms: default_feed_timeout_ms(),
+        }
+    }
+}
+
+impl Default for BybitFeedConfig {
+    fn default() -> Self {
+        Self {
+            symbol: None,
+            rest_url: default_bybit_rest_url(),
+            timeout_ms: default_feed_timeout_ms(),
+        }
+    }
+}
+
+impl Default for HttpConfig {
+    fn default() -> Self {
+        Self {
+            cors_allowed_origins: Vec::new(),
+            cors_allowed_methods: default_cors_allowed_methods(),
+            gzip: default_enabled(),
+            brotli: default_enabled(),
+            max_body_bytes: default_max_body_bytes(),
+            request_timeout_ms: default_request_timeout_ms(),
+        }
+    }
+}
+
+impl Default for AlertsConfig {
+    fn default() -> Self {
+        Self {
+            webhook_url: None,
+            secret: None,
+            max_retries: default_alert_max_retries(),
+            retry_backoff_ms: default_alert_retry_backoff_ms(),
+            dedup_window_secs: default_alert_dedup_window_secs(),
+            check_interval_secs: default_alert_check_interval_secs(),
+            rules: Vec::new(),
+        }
+    }
+}
@@ -0,0 +1,384 @@
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use toml_edit::{ArrayOfTables, DocumentMut, Item, Table, TableLike};
+
+use super::FeedConfig;
+
+/// A runtime change to the feed list, written back to the config file
+pub enum FeedChange<'a> {
+    Added(&'a FeedConfig),
+    Removed(&'a str),
+}
+
+#[derive(Serialize)]
+struct FeedsDocument<'a> {
+    feeds: &'a [FeedConfig],
+}
+
+#[derive(Deserialize)]
+struct ParsedFeeds {
+    feeds: Vec<FeedConfig>,
+}
+
+/// Apply `change` to the `[[feeds]]` tables of the config file in place. Only the
+/// added or removed table is touched; every other table, comment and blank line
+/// stays byte-for-byte, and values the file never set (such as environment
+/// overrides) are not written.
+pub fn save_feed_change<P: AsRef<Path>>(path: P, change: FeedChange<'_>) -> anyhow::Result<()> {
+    let path = path.as_ref();
+    let content = std::fs::read_to_string(path)?;
+    let updated = apply_feed_change(&content, change)?;
+
+    // Write a sibling file and rename it so readers never see a partial config
+    let temporary = path.with_extension("toml.tmp");
+    std::fs::write(&temporary, updated)?;
+    std::fs::rename(&temporary, path)?;
+    Ok(())
+}
+
+fn apply_feed_change(content: &str, change: FeedChange<'_>) -> anyhow::Result<String> {
+    let mut document: DocumentMut = content.parse()?;
+
+    match change {
+        FeedChange::Added(feed) => add_feed(&mut document, feed)?,
+        FeedChange::Removed(id) => remove_feed(&mut document, id)?,
+    }
+
+    Ok(document.to_string())
+}
+
+fn add_feed(document: &mut DocumentMut, feed: &FeedConfig) -> anyhow::Result<()> {
+    let mut table = minimal_table(feed)?;
+    table.decor_mut().set_prefix("\n");
+
+    // Tables render in position order; place the new one after the last feed
+    // (or at the end of the file) so it cannot land among other sections
+    let position = match document.get("feeds").and_then(Item::as_array_of_tables) {
+        Some(feeds) if !feeds.is_empty() => feeds.iter().last().map(max_position),
+        _ => None,
+    }
+    .unwrap_or_else(|| max_position(document.as_table()) + 1);
+    set_positions(&mut table, position);
+
+    match document.get_mut("feeds") {
+        Some(Item::ArrayOfTables(feeds)) => feeds.push(table),
+        Some(_) => anyhow::bail!("feeds is not an array of tables"),
+        None => {
+            let mut feeds = ArrayOfTables::new();
+            feeds.push(table);
+            document.insert("feeds", Item::ArrayOfTables(feeds));
+        }
+    }
+    Ok(())
+}
+
+fn remove_feed(document: &mut DocumentMut, id: &str) -> anyhow::Result<()> {
+    let feeds = document
+        .get_mut("feeds")
+        .and_then(Item::as_array_of_tables_mut)
+        .ok_or_else(|| anyhow::anyhow!("no [[feeds]] in the configuration"))?;
+    let index = feeds
+        .iter()
+        .position(|table| table_feed_id(table).as_deref() == Some(id))
+        .ok_or_else(|| anyhow::anyhow!("feed {} is not in the configuration file", id))?;
+
+    let removed = feeds.get(index).expect("index from position");
+    let after = max_position(removed);
+    let detached = detached_comments(removed);
+    feeds.remove(index);
+
+    // Comments above the removed table that are not attached to it, such as
+    // commented-out examples, move to whatever table followed it
+    if let Some(detached) = detached {
+        let next = feeds.get_mut(index);
+        match next {
+            Some(next) => prepend_prefix(next, &detached),
+            None => match next_table(document.as_table_mut(), after) {
+                Some(next) => prepend_prefix(next, &detached),
+                None => {
+                    let trailing = document.trailing().as_str().unwrap_or("").to_string();
+                    document.set_trailing(format!("{}{}", detached, trailing));
+                }
+            },
+        }
+    }
+    Ok(())
+}
+
+/// `feed` as a `[[feeds]]` table without the keys that only repeat defaults
+fn minimal_table(feed: &FeedConfig) -> anyhow::Result<Table> {
+    let mut document: DocumentMut = toml::to_string(&FeedsDocument {
+        feeds: std::slice::from_ref(feed),
+    })?
+    .parse()?;
+
+    // Deepest keys first, so a table emptied of defaults can go too
+    for path in key_paths(first_feed(&document)?, &mut Vec::new()) {
+        let mut candidate = document.clone();
+        remove_path(first_feed_mut(&mut candidate)?, &path);
+        // Required keys fail to parse without their value, so they stay
+        let parsed = toml::from_str::<ParsedFeeds>(&candidate.to_string());
+        if parsed.is_ok_and(|parsed| parsed.feeds.first() == Some(feed)) {
+            document = candidate;
+        }
+    }
+
+    Ok(first_feed(&document)?.clone())
+}
+
+fn first_feed(document: &DocumentMut) -> anyhow::Result<&Table> {
+    document
+        .get("feeds")
+        .and_then(Item::as_array_of_tables)
+        .and_then(|feeds| feeds.get(0))
+        .ok_or_else(|| anyhow::anyhow!("serialized feed has no [[feeds]] table"))
+}
+
+fn first_feed_mut(document: &mut DocumentMut) -> anyhow::Result<&mut Table> {
+    document
+        .get_mut("feeds")
+        .and_then(Item::as_array_of_tables_mut)
+        .and_then(|feeds| feeds.get_mut(0))
+        .ok_or_else(|| anyhow::anyhow!("serialized feed has no [[feeds]] table"))
+}
+
+fn key_paths(table: &dyn TableLike, prefix: &mut Vec<String>) -> Vec<Vec<String>> {
+    let mut paths = Vec::new();
+    for (key, item) in table.iter() {
+        prefix.push(key.to_string());
+        if let Some(child) = item.as_table_like() {
+            paths.extend(key_paths(child, prefix));
+        }
+        paths.push(prefix.clone());
+        prefix.pop();
+    }
+    paths
+}
+
+fn remove_path(table: &mut dyn TableLike, path: &[String]) {
+    match path {
+        [] => {}
+        [key] => {
+            table.remove(key);
+        }
+        [key, rest @ ..] => {
+            if let Some(child) = table.get_mut(key).and_then(Item::as_table_like_mut) {
+                remove_path(child, rest);
+            }
+        }
+    }
+}
+
+/// The table's id as `FeedConfig::id` derives it
+fn table_feed_id(table: &Table) -> Option<String> {
+    if let Some(id) = table.get("id").and_then(Item::as_str) {
+        return Some(id.to_string());
+    }
+
+    let field = |key: &str| table.get(key).and_then(Item::as_str);
+    Some(
+        format!(
+            "{}-{}-{}",
+            field("type")?,
+            field("base_token")?,
+            field("quote_token")?
+        )
+        .to_lowercase(),
+    )
+}
+
+/// The part of the table's leading decor before its last blank line, if it has
+/// any comments; the lines after the blank line describe the table itself
+fn detached_comments(table: &Table) -> Option<String> {
+    let prefix = table.decor().prefix()?.as_str()?;
+    let blank = prefix.rfind("\n\n")? + 1;
+
+    let detached = &prefix[..blank];
+    (!detached.trim().is_empty()).then(|| detached.to_string())
+}
+
+fn prepend_prefix(table: &mut Table, text: &str) {
+    let prefix = table
+        .decor()
+        .prefix()
+        .and_then(|prefix| prefix.as_str())
+        .unwrap_or("")
+        .to_string();
+    table.decor_mut().set_prefix(format!("{}{}", text, prefix));
+}
+
+/// Highest position of `table` and its sub-tables
+fn max_position(table: &Table) -> usize {
+    let mut max = table.position().unwrap_or(0);
+    for (_, item) in table.iter() {
+        match item {
+            Item::Table(child) => max = max.max(max_position(child)),
+            Item::ArrayOfTables(tables) => {
+                for child in tables.iter() {
+                    max = max.max(max_position(child));
+                }
+            }
+            _ => {}
+        }
+    }
+    max
+}
+
+fn set_positions(table: &mut Table, position: usize) {
+    table.set_position(position);
+    for (_, item) in table.iter_mut() {
+        if let Item::Table(child) = item {
+            set_positions(child, position);
+        }
+    }
+}
+
+/// The rendered table with the lowest position after `after`
+fn next_table(root: &mut Table, after: usize) -> Option<&mut Table> {
+    let mut best = None;
+    find_next(root, after, &mut Vec::new(), &mut best);
+    let (_, path) = best?;
+
+    let mut table = root;
+    for (key, index) in path {
+        table = match table.get_mut(&key)? {
+            Item::Table(child) => child,
+            Item::ArrayOfTables(tables) => tables.get_mut(index?)?,
+            _ => return None,
+        };
+    }
+    Some(table)
+}
+
+type TablePath = Vec<(String, Option<usize>)>;
+
+fn find_next(
+    table: &Table,
+    after: usize,
+    path: &mut TablePath,
+    best: &mut Option<(usize, TablePath)>,
+) {
+    if let Some(position) = table.position().filter(|position| *position > after) {
+        // Implicit tables such as `[sinks]` above `[sinks.nats]` are never rendered
+        if !table.is_implicit() && best.as_ref().is_none_or(|(best, _)| position < *best) {
+            *best = Some((position, path.clone()));
+        }
+    }
+
+    for (key, item) in table.iter() {
+        match item {
+            Item::Table(child) => {
+                path.push((key.to_string(), None));
+                find_next(child, after, path, best);
+                path.pop();
+            }
+            Item::ArrayOfTables(tables) => {
+                for (index, child) in tables.iter().enumerate() {
+                    path.push((key.to_string(), Some(index)));
+                    find_next(child, after, path, best);
+                    path.pop();
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = include_str!("../../config.toml");
+
+    const MOCK_FEED: &str = "\n# Mock feed for testing (works without external API)\n[[feeds]]\ntype = \"mock\"\nbase_token = \"SOL\"\nquote_token = \"USDC\"\ninterval_ms = 1500\npriority = 2\nenabled = false\n";
+
+    fn feed(toml: &str) -> FeedConfig {
+        let parsed: ParsedFeeds = toml::from_str(toml).unwrap();
+        parsed.feeds.into_iter().next().unwrap()
+    }
+
+    fn http_feed() -> FeedConfig {
+        feed(
+            r#"
+            [[feeds]]
+            id = "internal-sol"
+            type = "http_json"
+            base_token = "SOL"
+            quote_token = "USDC"
+            priority = 3
+
+            [feeds.http_json]
+            url = "https://prices.internal/v1/sol-usdc"
+            headers = { Authorization = "Bearer {api_key}" }
+            price_pointer = "/data/price"
+            "#,
+        )
+    }
+
+    #[test]
+    fn removing_a_feed_leaves_the_rest_of_the_file_untouched() {
+        let updated = apply_feed_change(CONFIG, FeedChange::Removed("mock-sol-usdc")).unwrap();
+
+        assert_eq!(updated, CONFIG.replacen(MOCK_FEED, "", 1));
+    }
+
+    #[test]
+    fn added_feed_goes_after_the_last_feed_without_defaults() {
+        let feed = http_feed();
+        let updated = apply_feed_change(CONFIG, FeedChange::Added(&feed)).unwrap();
+
+        let added = "\n[[feeds]]\nid = \"internal-sol\"\ntype = \"http_json\"\nbase_token = \"SOL\"\nquote_token = \"USDC\"\npriority = 3\n\n[feeds.http_json]\nurl = \"https://prices.internal/v1/sol-usdc\"\nprice_pointer = \"/data/price\"\n\n[feeds.http_json.headers]\nAuthorization = \"Bearer {api_key}\"\n";
+        let anchor = "priority = 3\nenabled = false\n\n# NATS sink";
+        let expected = CONFIG.replacen(
+            anchor,
+            &format!("priority = 3\nenabled = false\n{}\n# NATS sink", added),
+            1,
+        );
+        assert_eq!(updated, expected);
+
+        let parsed: ParsedFeeds = toml::from_str(&updated).unwrap();
+        assert_eq!(parsed.feeds.last(), Some(&feed));
+    }
+
+    #[test]
+    fn adding_then_removing_a_feed_restores_the_file() {
+        let feed = http_feed();
+        let added = apply_feed_change(CONFIG, FeedChange::Added(&feed)).unwrap();
+        let removed = apply_feed_change(&added, FeedChange::Removed("internal-sol")).unwrap();
+
+        assert_eq!(removed, CONFIG);
+    }
+
+    #[test]
+    fn removing_the_last_feed_moves_detached_comments_to_the_next_section() {
+        let content = "[[feeds]]\ntype = \"mock\"\nbase_token = \"SOL\"\nquote_token = \"USDC\"\n\n# [[feeds]]\n# type = \"example\"\n\n# Pyth stub\n[[feeds]]\ntype = \"pyth\"\nbase_token = \"SOL\"\nquote_token = \"USDC\"\n\n# NATS sink\n[sinks.nats]\nurl = \"nats://127.0.0.1:4222\"\n";
+        let updated = apply_feed_change(content, FeedChange::Removed("pyth-sol-usdc")).unwrap();
+
+        assert_eq!(
+            updated,
+            "[[feeds]]\ntype = \"mock\"\nbase_token = \"SOL\"\nquote_token = \"USDC\"\n\n# [[feeds]]\n# type = \"example\"\n\n# NATS sink\n[sinks.nats]\nurl = \"nats://127.0.0.1:4222\"\n"
+        );
+    }
+
+    #[test]
+    fn first_feed_goes_at_the_end_of_the_file() {
+        let content =
+            "[server]\nport = 8080\n\n# NATS sink\n[sinks.nats]\nurl = \"nats://127.0.0.1:4222\"\n";
+        let feed =
+            feed("[[feeds]]\ntype = \"mock\"\nbase_token = \"SOL\"\nquote_token = \"USDC\"\n");
+        let updated = apply_feed_change(content, FeedChange::Added(&feed)).unwrap();
+
+        assert_eq!(
+            updated,
+            format!(
+                "{}\n[[feeds]]\ntype = \"mock\"\nbase_token = \"SOL\"\nquote_token = \"USDC\"\n",
+                content
+            )
+        );
+    }
+
+    #[test]
+    fn unknown_feed_is_an_error() {
+        assert!(apply_feed_change(CONFIG, FeedChange::Removed("missing")).is_err());
+    }
+}
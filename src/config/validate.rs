@@ -0,0 +1,980 @@
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Range;
+use toml_edit::{ImDocument, Item, Table, TableLike, Value};
+
+use super::{Config, EnvOverride, FeedConfig};
+use crate::feeds::FEED_TYPES;
+
+/// One step of a key path such as `feeds[2].interval_ms`
+#[derive(Debug, Clone, PartialEq)]
+pub enum KeySegment {
+    Key(String),
+    Index(usize),
+}
+
+impl From<&str> for KeySegment {
+    fn from(key: &str) -> Self {
+        KeySegment::Key(key.to_string())
+    }
+}
+
+impl From<usize> for KeySegment {
+    fn from(index: usize) -> Self {
+        KeySegment::Index(index)
+    }
+}
+
+/// A single configuration problem
+#[derive(Debug)]
+pub struct ConfigIssue {
+    pub key: Vec<KeySegment>,
+    pub message: String,
+    /// 1-based line and column in the config file; `None` when the key is not
+    /// in the file (defaults and environment overrides)
+    pub location: Option<(usize, usize)>,
+    /// Environment variable the offending value came from
+    pub env_var: Option<String>,
+}
+
+impl ConfigIssue {
+    pub fn new(key: Vec<KeySegment>, message: impl Into<String>) -> Self {
+        Self {
+            key,
+            message: message.into(),
+            location: None,
+            env_var: None,
+        }
+    }
+
+    pub fn syntax(error: &toml::de::Error, content: &str) -> Self {
+        Self {
+            key: Vec::new(),
+            message: error.message().to_string(),
+            location: error.span().map(|span| line_column(content, span.start)),
+            env_var: None,
+        }
+    }
+
+    pub fn from_path_error(error: &serde_path_to_error::Error<toml::de::Error>) -> Self {
+        let key = error
+            .path()
+            .iter()
+            .filter_map(|segment| match segment {
+                serde_path_to_error::Segment::Seq { index } => Some(KeySegment::Index(*index)),
+                serde_path_to_error::Segment::Map { key } => Some(KeySegment::Key(key.clone())),
+                _ => None,
+            })
+            .collect();
+
+        Self::new(key, error.inner().message())
+    }
+
+    /// Point at the deepest part of `key` that appears in `content`; values
+    /// from the environment are not in the file and stay unlocated
+    pub fn located(mut self, content: &str) -> Self {
+        if self.location.is_none() && self.env_var.is_none() {
+            self.location = locate(content, &self.key).map(|span| line_column(content, span.start));
+        }
+        self
+    }
+}
+
+impl fmt::Display for ConfigIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some((line, column)) = self.location {
+            write!(f, "line {}, column {}: ", line, column)?;
+        }
+
+        for (position, segment) in self.key.iter().enumerate() {
+            match segment {
+                KeySegment::Key(key) if position == 0 => write!(f, "{}", key)?,
+                KeySegment::Key(key) => write!(f, ".{}", key)?,
+                KeySegment::Index(index) => write!(f, "[{}]", index)?,
+            }
+        }
+        if !self.key.is_empty() {
+            f.write_str(": ")?;
+        }
+
+        f.write_str(&self.message)?;
+        if let Some(name) = &self.env_var {
+            write!(f, " (set by {})", name)?;
+        }
+        Ok(())
+    }
+}
+
+enum Node<'a> {
+    Item(&'a Item),
+    Table(&'a Table),
+    Value(&'a Value),
+}
+
+fn locate(content: &str, key: &[KeySegment]) -> Option<Range<usize>> {
+    let document = ImDocument::parse(content).ok()?;
+    let mut node = Node::Table(document.as_table());
+    let mut span = None;
+
+    for segment in key {
+        let next = match (segment, node) {
+            (KeySegment::Key(name), node) => {
+                let table: &dyn TableLike = match node {
+                    Node::Item(item) => item.as_table_like(),
+                    Node::Table(table) => Some(table as &dyn TableLike),
+                    Node::Value(value) => value.as_inline_table().map(|t| t as &dyn TableLike),
+                }?;
+                table
+                    .get_key_value(name)
+                    .map(|(key, item)| (key.span(), Node::Item(item)))
+            }
+            (KeySegment::Index(index), Node::Item(Item::ArrayOfTables(tables))) => tables
+                .get(*index)
+                .map(|table| (table.span(), Node::Table(table))),
+            (KeySegment::Index(index), Node::Item(Item::Value(Value::Array(values))))
+            | (KeySegment::Index(index), Node::Value(Value::Array(values))) => values
+                .get(*index)
+                .map(|value| (value.span(), Node::Value(value))),
+            _ => None,
+        };
+
+        let Some((found, child)) = next else {
+            break;
+        };
+        span = found.or(span);
+        node = child;
+    }
+
+    span
+}
+
+fn line_column(content: &str, offset: usize) -> (usize, usize) {
+    let before = &content[..offset.min(content.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before.len() - before.rfind('\n').map_or(0, |newline| newline + 1) + 1;
+    (line, column)
+}
+
+/// Upper bound on deserialization retries, far above any real config's key count
+const MAX_PARSE_ATTEMPTS: usize = 1000;
+
+/// Deserialize `document` and validate the result, collecting every problem.
+///
+/// serde stops at the first error, so each failing key is reported and removed
+/// and deserialization retried: unknown and mistyped keys are dropped (falling
+/// back to their defaults), and an array entry that still cannot be built is
+/// dropped as a whole. The semantic pass then runs on everything that parsed,
+/// with issue indexes mapped back to the entries' positions in the file.
+///
+/// An override that a string field rejected as another type is retried as the
+/// string it was given, and problems with overridden values name the variable.
+pub(super) fn parse_document(
+    mut document: toml::Value,
+    overrides: &[EnvOverride],
+) -> Result<Config, Vec<ConfigIssue>> {
+    let mut issues = Vec::new();
+    let mut removed = RemovedEntries::default();
+    let mut removed_keys: Vec<Vec<KeySegment>> = Vec::new();
+    let mut retyped: Vec<&str> = Vec::new();
+    let from_env = |mut issue: ConfigIssue| {
+        issue.env_var = overrides
+            .iter()
+            .rev()
+            .find(|o| issue.key.starts_with(&o.key))
+            .map(|o| o.name.clone());
+        issue
+    };
+
+    for _ in 0..MAX_PARSE_ATTEMPTS {
+        let error = match serde_path_to_error::deserialize::<_, Config>(document.clone()) {
+            Ok(config) => {
+                issues.extend(config.validate().into_iter().map(|mut issue| {
+                    issue.key = removed.original(&issue.key);
+                    from_env(issue)
+                }));
+                return if issues.is_empty() {
+                    Ok(config)
+                } else {
+                    Err(issues)
+                };
+            }
+            Err(error) => error,
+        };
+
+        let issue = ConfigIssue::from_path_error(&error);
+        let path = issue.key.clone();
+        let original = removed.original(&path);
+
+        if issue.message.ends_with("expected a string") {
+            let retry = overrides
+                .iter()
+                .find(|o| o.key == original && !retyped.contains(&o.name.as_str()));
+            if let Some(retry) = retry {
+                if let Some(value) = node_at(&mut document, &path) {
+                    *value = toml::Value::String(retry.raw.clone());
+                    retyped.push(&retry.name);
+                    continue;
+                }
+            }
+        }
+
+        // A mistyped required key comes back as missing once removed; it is already reported
+        let already_reported = missing_field(issue.message.as_str()).is_some_and(|field| {
+            let mut key = path.clone();
+            key.push(KeySegment::Key(field.to_string()));
+            removed_keys.contains(&key)
+        });
+        if !already_reported {
+            issues.push(from_env(ConfigIssue {
+                key: original.clone(),
+                ..issue
+            }));
+        }
+
+        match path.split_last() {
+            Some((KeySegment::Index(_), array)) => {
+                let Some(KeySegment::Index(original)) = original.last().cloned() else {
+                    break;
+                };
+                if !remove_at(&mut document, &path) {
+                    break;
+                }
+                removed.record(array, original);
+            }
+            Some(_) if remove_at(&mut document, &path) => removed_keys.push(path),
+            _ => break,
+        }
+    }
+
+    Err(issues)
+}
+
+/// The field named in serde's "missing field `name`" message
+fn missing_field(message: &str) -> Option<&str> {
+    message
+        .strip_prefix("missing field `")
+        .and_then(|rest| rest.strip_suffix('`'))
+}
+
+fn node_at<'a>(document: &'a mut toml::Value, path: &[KeySegment]) -> Option<&'a mut toml::Value> {
+    path.iter()
+        .try_fold(document, |node, segment| match (segment, node) {
+            (KeySegment::Key(key), toml::Value::Table(table)) => table.get_mut(key),
+            (KeySegment::Index(index), toml::Value::Array(items)) => items.get_mut(*index),
+            _ => None,
+        })
+}
+
+fn remove_at(document: &mut toml::Value, path: &[KeySegment]) -> bool {
+    let Some((last, parents)) = path.split_last() else {
+        return false;
+    };
+    let Some(node) = node_at(document, parents) else {
+        return false;
+    };
+
+    match (last, node) {
+        (KeySegment::Key(key), toml::Value::Table(table)) => table.remove(key).is_some(),
+        (KeySegment::Index(index), toml::Value::Array(items)) if *index < items.len() => {
+            items.remove(*index);
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Original positions of array entries dropped while parsing, per array path
+#[derive(Default)]
+struct RemovedEntries(HashMap<String, Vec<usize>>);
+
+impl RemovedEntries {
+    fn record(&mut self, array: &[KeySegment], original: usize) {
+        let removed = self.0.entry(path_key(array)).or_default();
+        removed.push(original);
+        removed.sort_unstable();
+    }
+
+    /// `key` with every array index mapped from the trimmed document back to the file
+    fn original(&self, key: &[KeySegment]) -> Vec<KeySegment> {
+        key.iter()
+            .enumerate()
+            .map(|(position, segment)| match segment {
+                KeySegment::Index(index) => {
+                    let mut index = *index;
+                    for removed in self
+                        .0
+                        .get(&path_key(&key[..position]))
+                        .into_iter()
+                        .flatten()
+                    {
+                        if *removed <= index {
+                            index += 1;
+                        }
+                    }
+                    KeySegment::Index(index)
+                }
+                key => key.clone(),
+            })
+            .collect()
+    }
+}
+
+fn path_key(path: &[KeySegment]) -> String {
+    ConfigIssue::new(path.to_vec(), "").to_string()
+}
+
+impl FeedConfig {
+    /// Problems with this feed as `(field, message)`
+    pub fn validate(&self) -> Vec<(&'static str, String)> {
+        let mut problems = Vec::new();
+
+        if self.id.as_deref().is_some_and(str::is_empty) {
+            problems.push(("id", "must not be empty".to_string()));
+        }
+        if !FEED_TYPES.contains(&self.feed_type.as_str()) {
+            problems.push((
+                "type",
+                format!(
+                    "unknown feed type \"{}\", expected one of {}",
+                    self.feed_type,
+                    FEED_TYPES.join(", ")
+                ),
+            ));
+        }
+        if self.base_token.is_empty() {
+            problems.push(("base_token", "must not be empty".to_string()));
+        }
+        if self.quote_token.is_empty() {
+            problems.push(("quote_token", "must not be empty".to_string()));
+        } else if self.base_token.eq_ignore_ascii_case(&self.quote_token) {
+            problems.push(("quote_token", "must differ from base_token".to_string()));
+        }
+        if self.interval_ms == 0 {
+            problems.push(("interval_ms", "must be greater than zero".to_string()));
+        }
+        if self.api_key_env.is_some() && self.api_key_file.is_some() {
+            problems.push((
+                "api_key_file",
+                "set only one of api_key_env and api_key_file".to_string(),
+            ));
+        }
+
+        if let Some(settings) = self.section("http_json", &self.http_json, true, &mut problems) {
+            validate_endpoint(
+                ("http_json.url", &settings.url),
+                ("http_json.timeout_ms", settings.timeout_ms),
+                &mut problems,
+            );
+            if !is_json_pointer(&settings.price_pointer) {
+                problems.push(("http_json.price_pointer", POINTER_MESSAGE.to_string()));
+            }
+            if settings
+                .timestamp_pointer
+                .as_deref()
+                .is_some_and(|pointer| !is_json_pointer(pointer))
+            {
+                problems.push(("http_json.timestamp_pointer", POINTER_MESSAGE.to_string()));
+            }
+            if settings.scale.is_some_and(|scale| scale <= Decimal::ZERO) {
+                problems.push(("http_json.scale", "must be positive".to_string()));
+            }
+        }
+
+        if let Some(settings) = self.section("coinbase", &self.coinbase, false, &mut problems) {
+            if settings.product_id.as_deref().is_some_and(str::is_empty) {
+                problems.push(("coinbase.product_id", "must not be empty".to_string()));
+            }
+            validate_endpoint(
+                ("coinbase.rest_url", &settings.rest_url),
+                ("coinbase.timeout_ms", settings.timeout_ms),
+                &mut problems,
+            );
+            if !is_ws_url(&settings.ws_url) {
+                problems.push(("coinbase.ws_url", "must be a ws(s) URL".to_string()));
+            }
+        }
+
+        if let Some(settings) = self.section("kraken", &self.kraken, false, &mut problems) {
+            if settings.pair.as_deref().is_some_and(str::is_empty) {
+                problems.push(("kraken.pair", "must not be empty".to_string()));
+            }
+            validate_endpoint(
+                ("kraken.rest_url", &settings.rest_url),
+                ("kraken.timeout_ms", settings.timeout_ms),
+                &mut problems,
+            );
+        }
+
+        if let Some(settings) = self.section("solana_pool", &self.solana_pool, true, &mut problems)
+        {
+            if !bs58::decode(&settings.pool)
+                .into_vec()
+                .is_ok_and(|bytes| bytes.len() == 32)
+            {
+                problems.push((
+                    "solana_pool.pool",
+                    "must be a base58 account address".to_string(),
+                ));
+            }
+            validate_endpoint(
+                ("solana_pool.rpc_url", &settings.rpc_url),
+                ("solana_pool.timeout_ms", settings.timeout_ms),
+                &mut problems,
+            );
+        }
+
+        if let Some(settings) =
+            self.section("jupiter_quote", &self.jupiter_quote, true, &mut problems)
+        {
+            if settings.amount <= Decimal::ZERO {
+                problems.push(("jupiter_quote.amount", "must be positive".to_string()));
+            }
+            validate_endpoint(
+                ("jupiter_quote.api_url", &settings.api_url),
+                ("jupiter_quote.timeout_ms", settings.timeout_ms),
+                &mut problems,
+            );
+        }
+
+        if let Some(settings) = self.section("chainlink", &self.chainlink, true, &mut problems) {
+            validate_endpoint(
+                ("chainlink.rpc_url", &settings.rpc_url),
+                ("chainlink.timeout_ms", settings.timeout_ms),
+                &mut problems,
+            );
+            if !is_evm_address(&settings.aggregator) {
+                problems.push((
+                    "chainlink.aggregator",
+                    "must be a 0x-prefixed 20-byte hex address".to_string(),
+                ));
+            }
+            if settings.staleness_threshold_secs == 0 {
+                problems.push((
+                    "chainlink.staleness_threshold_secs",
+                    "must be greater than zero".to_string(),
+                ));
+            }
+        }
+
+        if let Some(settings) = self.section("okx", &self.okx, false, &mut problems) {
+            if settings.inst_id.as_deref().is_some_and(str::is_empty) {
+                problems.push(("okx.inst_id", "must not be empty".to_string()));
+            }
+            validate_endpoint(
+                ("okx.rest_url", &settings.rest_url),
+                ("okx.timeout_ms", settings.timeout_ms),
+                &mut problems,
+            );
+        }
+
+        if let Some(settings) = self.section("bybit", &self.bybit, false, &mut problems) {
+            if settings.symbol.as_deref().is_some_and(str::is_empty) {
+                problems.push(("bybit.symbol", "must not be empty".to_string()));
+            }
+            validate_endpoint(
+                ("bybit.rest_url", &settings.rest_url),
+                ("bybit.timeout_ms", settings.timeout_ms),
+                &mut problems,
+            );
+        }
+
+        problems
+    }
+
+    /// The feed-specific section named after `feed_type`, which is only valid
+    /// for feeds of that type and must be present when `required`
+    fn section<'a, T>(
+        &self,
+        feed_type: &'static str,
+        section: &'a Option<T>,
+        required: bool,
+        problems: &mut Vec<(&'static str, String)>,
+    ) -> Option<&'a T> {
+        match (self.feed_type == feed_type, section) {
+            (true, None) if required => problems.push((
+                feed_type,
+                format!("section is required for type \"{}\"", feed_type),
+            )),
+            (false, Some(_)) => problems.push((
+                feed_type,
+                format!("section is only valid for type \"{}\"", feed_type),
+            )),
+            _ => {}
+        }
+
+        section.as_ref().filter(|_| self.feed_type == feed_type)
+    }
+}
+
+const MAX_ALERT_RETRIES: u32 = 10;
+const MAX_ALERT_RETRY_BACKOFF_MS: u64 = 60_000;
+
+const POINTER_MESSAGE: &str = "must be a JSON pointer such as /data/price";
+
+/// The URL and request timeout every HTTP-based feed section carries, as
+/// `(field, value)` pairs
+fn validate_endpoint(
+    (url_field, url): (&'static str, &str),
+    (timeout_field, timeout_ms): (&'static str, u64),
+    problems: &mut Vec<(&'static str, String)>,
+) {
+    if !is_http_url(url) {
+        problems.push((url_field, "must be an http(s) URL".to_string()));
+    }
+    if timeout_ms == 0 {
+        problems.push((timeout_field, "must be greater than zero".to_string()));
+    }
+}
+
+fn is_http_url(url: &str) -> bool {
+    url.starts_with("http://") || url.starts_with("https://")
+}
+
+fn is_evm_address(address: &str) -> bool {
+    address
+        .strip_prefix("0x")
+        .is_some_and(|hex| hex.len() == 40 && hex.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+fn is_ws_url(url: &str) -> bool {
+    url.starts_with("ws://") || url.starts_with("wss://")
+}
+
+/// RFC 6901: empty (the whole document) or starting with `/`
+fn is_json_pointer(pointer: &str) -> bool {
+    pointer.is_empty() || pointer.starts_with('/')
+}
+
+impl Config {
+    /// Every semantic problem, without file locations; empty when valid.
+    /// Feed construction and key files are checked where they are loaded.
+    pub fn validate(&self) -> Vec<ConfigIssue> {
+        let mut issues = Vec::new();
+
+        if self.server.port == 0 {
+            issues.push(ConfigIssue::new(
+                vec!["server".into(), "port".into()],
+                "must be greater than zero",
+            ));
+        }
+        if self.server.staleness_threshold_secs == 0 {
+            issues.push(ConfigIssue::new(
+                vec!["server".into(), "staleness_threshold_secs".into()],
+                "must be greater than zero",
+            ));
+        }
+        if self.server.http.max_body_bytes == 0 {
+            issues.push(ConfigIssue::new(
+                vec!["server".into(), "http".into(), "max_body_bytes".into()],
+                "must be greater than zero",
+            ));
+        }
+        if self.server.http.request_timeout_ms == 0 {
+            issues.push(ConfigIssue::new(
+                vec!["server".into(), "http".into(), "request_timeout_ms".into()],
+                "must be greater than zero",
+            ));
+        }
+
+        let mut feed_ids: Vec<String> = Vec::new();
+        let mut feed_sources: Vec<(String, String)> = Vec::new();
+        for (index, feed) in self.feeds.iter().enumerate() {
+            for (field, message) in feed.validate() {
+                let mut key = vec!["feeds".into(), index.into()];
+                key.extend(field.split('.').map(KeySegment::from));
+                issues.push(ConfigIssue::new(key, message));
+            }
+
+            let id = feed.id();
+            if feed_ids.contains(&id) {
+                issues.push(ConfigIssue::new(
+                    vec!["feeds".into(), index.into()],
+                    format!("duplicate feed id \"{}\"", id),
+                ));
+            }
+            feed_ids.push(id);
+
+            let source = (feed.source(), feed.pair().to_uppercase());
+            if feed_sources.contains(&source) {
+                issues.push(ConfigIssue::new(
+                    vec!["feeds".into(), index.into()],
+                    format!("duplicate {} feed for {}", source.0, source.1),
+                ));
+            }
+            feed_sources.push(source);
+        }
+
+        let mut pairs: Vec<String> = Vec::new();
+        for (index, rules) in self.pairs.iter().enumerate() {
+            let key = |field: &str| vec!["pairs".into(), index.into(), field.into()];
+            let pair = rules.pair.to_uppercase();
+
+            match pair.split_once('/') {
+                Some((base, quote)) if !base.is_empty() && !quote.is_empty() => {}
+                _ => issues.push(ConfigIssue::new(key("pair"), "expected BASE/QUOTE")),
+            }
+            if pairs.contains(&pair) {
+                issues.push(ConfigIssue::new(
+                    key("pair"),
+                    format!("duplicate pair \"{}\"", rules.pair),
+                ));
+            }
+            if rules.staleness_threshold_secs == Some(0) {
+                issues.push(ConfigIssue::new(
+                    key("staleness_threshold_secs"),
+                    "must be greater than zero",
+                ));
+            }
+            if rules.min_price.is_some_and(|min| min <= Decimal::ZERO) {
+                issues.push(ConfigIssue::new(key("min_price"), "must be positive"));
+            }
+            if let (Some(min), Some(max)) = (rules.min_price, rules.max_price) {
+                if min >= max {
+                    issues.push(ConfigIssue::new(
+                        key("max_price"),
+                        "must be greater than min_price",
+                    ));
+                }
+            }
+            if rules
+                .max_move_percent
+                .is_some_and(|percent| percent <= Decimal::ZERO)
+            {
+                issues.push(ConfigIssue::new(
+                    key("max_move_percent"),
+                    "must be positive",
+                ));
+            }
+            if rules.min_sources == 0 {
+                issues.push(ConfigIssue::new(key("min_sources"), "must be at least 1"));
+            }
+            pairs.push(pair);
+        }
+
+        for (symbol, token) in &self.tokens {
+            // rust_decimal cannot represent more than 28 fractional digits
+            if token.decimals > 28 {
+                issues.push(ConfigIssue::new(
+                    vec!["tokens".into(), symbol.as_str().into(), "decimals".into()],
+                    "must be at most 28",
+                ));
+            }
+        }
+
+        for (index, pair) in self.health.required_pairs.iter().enumerate() {
+            let configured = self
+                .feeds
+                .iter()
+                .any(|feed| feed.pair().eq_ignore_ascii_case(pair));
+            if !configured {
+                issues.push(ConfigIssue::new(
+                    vec!["health".into(), "required_pairs".into(), index.into()],
+                    format!("no feed configured for {}", pair),
+                ));
+            }
+        }
+
+        let alerts = |field: &str| vec!["alerts".into(), field.into()];
+        if self.alerts.check_interval_secs == 0 {
+            issues.push(ConfigIssue::new(
+                alerts("check_interval_secs"),
+                "must be greater than zero",
+            ));
+        }
+        if self.alerts.max_retries > MAX_ALERT_RETRIES {
+            issues.push(ConfigIssue::new(
+                alerts("max_retries"),
+                format!("must be at most {}", MAX_ALERT_RETRIES),
+            ));
+        }
+        if !(1..=MAX_ALERT_RETRY_BACKOFF_MS).contains(&self.alerts.retry_backoff_ms) {
+            issues.push(ConfigIssue::new(
+                alerts("retry_backoff_ms"),
+                format!("must be between 1 and {}", MAX_ALERT_RETRY_BACKOFF_MS),
+            ));
+        }
+
+        let mut rule_ids: Vec<&str> = Vec::new();
+        for (index, rule) in self.alerts.rules.iter().enumerate() {
+            let key = vec!["alerts".into(), "rules".into(), index.into()];
+            if rule_ids.contains(&rule.id.as_str()) {
+                issues.push(ConfigIssue::new(
+                    key.clone(),
+                    format!("duplicate rule id \"{}\"", rule.id),
+                ));
+            }
+            if let Err(e) = rule.validate() {
+                issues.push(ConfigIssue::new(key, e.to_string()));
+            }
+            rule_ids.push(&rule.id);
+        }
+
+        if let Some(admin) = &self.admin {
+            if admin.token.expose().is_empty() {
+                issues.push(ConfigIssue::new(
+                    vec!["admin".into(), "token".into()],
+                    "must not be empty",
+                ));
+            }
+        }
+
+        let mut key_names: Vec<&str> = Vec::new();
+        for (index, api_key) in self.api_keys.iter().enumerate() {
+            let key = |field: &str| vec!["api_keys".into(), index.into(), field.into()];
+            if key_names.contains(&api_key.name.as_str()) {
+                issues.push(ConfigIssue::new(
+                    key("name"),
+                    format!("duplicate name \"{}\"", api_key.name),
+                ));
+            }
+            if api_key.key.expose().is_empty() {
+                issues.push(ConfigIssue::new(key("key"), "must not be empty"));
+            }
+            if api_key.requests_per_second == 0 {
+                issues.push(ConfigIssue::new(
+                    key("requests_per_second"),
+                    "must be greater than zero",
+                ));
+            }
+            key_names.push(&api_key.name);
+        }
+
+        issues
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::apply_env_overrides;
+
+    /// What `Config::load` reports for `content` with the given environment
+    fn parse(content: &str, vars: &[(&str, &str)]) -> Result<Config, Vec<String>> {
+        let mut document = toml::Value::Table(toml::from_str(content).unwrap());
+        let vars = vars
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()));
+        let overrides = apply_env_overrides(&mut document, vars).unwrap();
+
+        parse_document(document, &overrides).map_err(|issues| {
+            let mut issues: Vec<ConfigIssue> = issues
+                .into_iter()
+                .map(|issue| issue.located(content))
+                .collect();
+            issues.sort_by_key(|issue| issue.location.unwrap_or((usize::MAX, 0)));
+            issues.iter().map(ConfigIssue::to_string).collect()
+        })
+    }
+
+    #[test]
+    fn every_problem_is_reported_in_one_run() {
+        let content = r#"
+[server]
+port = "eighty"
+colour = "blue"
+
+[[feeds]]
+type = "mock"
+base_token = "SOL"
+quote_token = "USDC"
+interval_ms = "fast"
+
+[[feeds]]
+type = "mock"
+base_token = "ETH"
+quote_token = "USDC"
+interval_ms = 0
+"#;
+
+        assert_eq!(
+            parse(content, &[]).unwrap_err(),
+            [
+                r#"line 3, column 1: server.port: invalid type: string "eighty", expected u16"#,
+                "line 4, column 1: server.colour: unknown field `colour`, expected one of `port`, `staleness_threshold_secs`, `http`",
+                r#"line 10, column 1: feeds[0].interval_ms: invalid type: string "fast", expected u64"#,
+                "line 16, column 1: feeds[1].interval_ms: must be greater than zero",
+            ]
+        );
+    }
+
+    #[test]
+    fn unknown_fields_in_nested_tables_are_rejected() {
+        let content = r#"
+[server.http]
+gzip = true
+zip = true
+
+[[feeds]]
+type = "http_json"
+base_token = "SOL"
+quote_token = "USDC"
+
+[feeds.http_json]
+url = "https://prices.internal/sol"
+price_pointer = "/price"
+bogus = 1
+"#;
+
+        assert_eq!(
+            parse(content, &[]).unwrap_err(),
+            [
+                "line 4, column 1: server.http.zip: unknown field `zip`, expected one of `cors_allowed_origins`, `cors_allowed_methods`, `gzip`, `brotli`, `max_body_bytes`, `request_timeout_ms`",
+                "line 14, column 1: feeds[0].http_json.bogus: unknown field `bogus`, expected one of `url`, `headers`, `price_pointer`, `timestamp_pointer`, `timestamp_format`, `scale`, `invert`, `timeout_ms`",
+            ]
+        );
+    }
+
+    #[test]
+    fn duplicate_feed_ids_keep_their_file_index() {
+        // The first entry cannot be built and is dropped; later indexes still match the file
+        let content = r#"
+[server]
+port = 8080
+
+[[feeds]]
+type = "mock"
+base_token = "SOL"
+
+[[feeds]]
+id = "sol"
+type = "mock"
+base_token = "SOL"
+quote_token = "USDC"
+
+[[feeds]]
+id = "sol"
+type = "mock"
+base_token = "SOL"
+quote_token = "USDT"
+"#;
+
+        assert_eq!(
+            parse(content, &[]).unwrap_err(),
+            [
+                "line 5, column 1: feeds[0]: missing field `quote_token`",
+                r#"line 15, column 1: feeds[2]: duplicate feed id "sol""#,
+            ]
+        );
+    }
+
+    #[test]
+    fn endpoint_sections_check_url_and_timeout() {
+        let content = r#"
+[server]
+port = 8080
+
+[[feeds]]
+type = "kraken"
+base_token = "SOL"
+quote_token = "USD"
+
+[feeds.kraken]
+rest_url = "api.kraken.com"
+timeout_ms = 0
+
+[[feeds]]
+type = "chainlink"
+base_token = "ETH"
+quote_token = "USD"
+
+[feeds.chainlink]
+rpc_url = "ws://localhost:8545"
+aggregator = "0x5f4eC3Df9cbd43714FE2740f5E3616155c5b8419"
+timeout_ms = 0
+"#;
+
+        assert_eq!(
+            parse(content, &[]).unwrap_err(),
+            [
+                "line 11, column 1: feeds[0].kraken.rest_url: must be an http(s) URL",
+                "line 12, column 1: feeds[0].kraken.timeout_ms: must be greater than zero",
+                "line 20, column 1: feeds[1].chainlink.rpc_url: must be an http(s) URL",
+                "line 22, column 1: feeds[1].chainlink.timeout_ms: must be greater than zero",
+            ]
+        );
+    }
+
+    const MINIMAL: &str = r#"
+[server]
+port = 8080
+
+[admin]
+token = "file-token"
+
+[[feeds]]
+type = "mock"
+base_token = "SOL"
+quote_token = "USDC"
+"#;
+
+    #[test]
+    fn overrides_replace_file_values() {
+        let config = parse(
+            MINIMAL,
+            &[
+                ("RATE_RELAY__SERVER__PORT", "9090"),
+                ("RATE_RELAY__FEEDS__0__ENABLED", "false"),
+                ("RATE_RELAY__Server__Staleness_Threshold_Secs", "5"),
+                ("UNRELATED", "1"),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(config.server.port, 9090);
+        assert_eq!(config.server.staleness_threshold_secs, 5);
+        assert!(!config.feeds[0].enabled);
+    }
+
+    #[test]
+    fn numeric_override_of_a_string_stays_a_string() {
+        let config = parse(MINIMAL, &[("RATE_RELAY__ADMIN__TOKEN", "123456")]).unwrap();
+        assert_eq!(config.admin.unwrap().token.expose(), "123456");
+
+        // Keys the file does not set are retried as a string when one is expected
+        let config = parse(MINIMAL, &[("RATE_RELAY__FEEDS__0__ID", "42")]).unwrap();
+        assert_eq!(config.feeds[0].id(), "42");
+    }
+
+    #[test]
+    fn bad_override_names_the_variable() {
+        assert_eq!(
+            parse(
+                MINIMAL,
+                &[
+                    ("RATE_RELAY__SERVER__PORT", "abc"),
+                    ("RATE_RELAY__FEEDS__0__INTERVAL_MS", "0"),
+                ]
+            )
+            .unwrap_err(),
+            [
+                r#"server.port: invalid type: string "abc", expected u16 (set by RATE_RELAY__SERVER__PORT)"#,
+                "feeds[0].interval_ms: must be greater than zero (set by RATE_RELAY__FEEDS__0__INTERVAL_MS)",
+            ]
+        );
+    }
+
+    #[test]
+    fn override_paths_must_exist_in_arrays() {
+        let mut document = toml::Value::Table(toml::from_str(MINIMAL).unwrap());
+        let vars = [
+            ("RATE_RELAY__FEEDS__3__ENABLED", "false"),
+            ("RATE_RELAY__SERVER____PORT", "1"),
+        ];
+
+        for (name, value) in vars {
+            let error = apply_env_overrides(
+                &mut document,
+                std::iter::once((name.to_string(), value.to_string())),
+            )
+            .unwrap_err();
+            assert!(error.starts_with(name), "{}", error);
+        }
+    }
+
+    #[test]
+    fn valid_document_parses() {
+        let config = parse(include_str!("../../config.toml"), &[]).unwrap();
+        assert_eq!(config.server.port, 8080);
+    }
+}
@@ -1,5 +1,7 @@
 use thiserror::Error;
 
+use crate::config::ConfigIssue;
+
 #[derive(Error, Debug)]
 pub enum FeedError {
     #[error("HTTP request failed: {0}")]
@@ -13,4 +15,129 @@ pub enum FeedError {
 
     #[error("Feed not implemented: {0}")]
     NotImplemented(String),
+
+    #[error("Invalid feed configuration: {0}")]
+    Config(String),
+
+    #[error("Price rejected: {0}")]
+    Rejected(String),
+
+    #[error("WebSocket stream error: {0}")]
+    Stream(String),
+}
+
+#[derive(Error, Debug)]
+pub enum SinkError {
+    #[error("Failed to connect: {0}")]
+    Connect(String),
+
+    #[error("Failed to publish: {0}")]
+    Publish(String),
+
+    #[error("Failed to serialize payload: {0}")]
+    Serialize(#[from] serde_json::Error),
+}
+
+#[derive(Error, Debug)]
+pub enum AlertError {
+    #[error("Alert rule already exists: {0}")]
+    DuplicateRule(String),
+
+    #[error("Alert rule not found: {0}")]
+    RuleNotFound(String),
+
+    #[error("Invalid alert rule: {0}")]
+    InvalidRule(String),
+}
+
+#[derive(Error, Debug)]
+pub enum RegistryError {
+    #[error("Feed already exists: {0}")]
+    DuplicateFeed(String),
+
+    #[error("Feed not found: {0}")]
+    FeedNotFound(String),
+
+    #[error("Feed is disabled: {0}")]
+    FeedDisabled(String),
+
+    #[error("Invalid feed: {0}")]
+    InvalidFeed(String),
+
+    #[error("Feed scheduler is not running: {0}")]
+    SchedulerStopped(String),
+
+    #[error("Failed to persist feeds: {0}")]
+    Persist(String),
+
+    #[error("Fetch failed: {0}")]
+    Fetch(#[from] FeedError),
+}
+
+/// Why a price could not be served for a pair
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PriceError {
+    #[error("Invalid pair, expected BASE/QUOTE")]
+    InvalidPair,
+
+    #[error("No price data available")]
+    Missing,
+
+    #[error("Price data is stale")]
+    Stale,
+
+    #[error("Not enough fresh sources")]
+    InsufficientSources,
+
+    #[error("API key is not allowed to read this pair")]
+    Forbidden,
+}
+
+impl PriceError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            PriceError::InvalidPair => "invalid_pair",
+            PriceError::Missing => "missing",
+            PriceError::Stale => "stale",
+            PriceError::InsufficientSources => "insufficient_sources",
+            PriceError::Forbidden => "forbidden",
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum SigningError {
+    #[error("Failed to read key file: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Invalid key: {0}")]
+    InvalidKey(String),
+
+    #[error("Signature verification failed")]
+    InvalidSignature,
+}
+
+#[derive(Error, Debug)]
+pub enum ConfigError {
+    #[error("Failed to read {path}: {source}")]
+    Read {
+        path: String,
+        source: std::io::Error,
+    },
+
+    #[error("Invalid environment override {0}")]
+    Override(String),
+
+    #[error("Invalid configuration in {path}:{}", format_issues(.issues))]
+    Invalid {
+        path: String,
+        issues: Vec<ConfigIssue>,
+    },
+}
+
+fn format_issues(issues: &[ConfigIssue]) -> String {
+    issues
+        .iter()
+        .map(|issue| format!("\n  {}", issue))
+        .collect()
 }
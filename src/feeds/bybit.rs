@@ -0,0 +1,145 @@
+use async_trait::async_trait;
+use chrono::DateTime;
+use reqwest::Client;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::config::FeedConfig;
+use crate::error::FeedError;
+use crate::models::PriceData;
+
+use super::PriceFeed;
+
+/// Bybit `retCode` for invalid request parameters, including an unknown symbol
+const INVALID_PARAMETER: i64 = 10001;
+
+/// Bybit spot price feed using `/v5/market/tickers`
+pub struct BybitFeed {
+    client: Client,
+    pair: String,
+    priority: u32,
+    symbol: String,
+    ticker_url: String,
+    timeout: Duration,
+}
+
+/// Bybit v5 response envelope; `retCode` is 0 on success
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BybitResponse {
+    ret_code: i64,
+    #[serde(default)]
+    ret_msg: String,
+    result: Option<TickerList>,
+    /// Server time in milliseconds; tickers carry no timestamp of their own
+    time: i64,
+}
+
+#[derive(Debug, Deserialize)]
+struct TickerList {
+    #[serde(default)]
+    list: Vec<BybitTicker>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BybitTicker {
+    last_price: String,
+}
+
+impl BybitFeed {
+    pub fn new(config: &FeedConfig, client: Client) -> Self {
+        let settings = config.bybit.clone().unwrap_or_default();
+        let symbol = settings.symbol.unwrap_or_else(|| {
+            format!(
+                "{}{}",
+                config.base_token.to_uppercase(),
+                config.quote_token.to_uppercase()
+            )
+        });
+
+        Self {
+            client,
+            pair: config.pair(),
+            priority: config.priority,
+            symbol,
+            ticker_url: format!(
+                "{}/v5/market/tickers",
+                settings.rest_url.trim_end_matches('/')
+            ),
+            timeout: Duration::from_millis(settings.timeout_ms),
+        }
+    }
+}
+
+#[async_trait]
+impl PriceFeed for BybitFeed {
+    fn name(&self) -> &str {
+        "bybit"
+    }
+
+    fn pair(&self) -> &str {
+        &self.pair
+    }
+
+    fn priority(&self) -> u32 {
+        self.priority
+    }
+
+    async fn fetch_price(&self) -> Result<PriceData, FeedError> {
+        let response: BybitResponse = self
+            .client
+            .get(&self.ticker_url)
+            .query(&[("category", "spot"), ("symbol", &self.symbol)])
+            .timeout(self.timeout)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        match response.ret_code {
+            0 => {}
+            INVALID_PARAMETER => {
+                return Err(FeedError::Config(format!(
+                    "invalid Bybit symbol {}: {}",
+                    self.symbol, response.ret_msg
+                )))
+            }
+            code => {
+                return Err(FeedError::InvalidData(format!(
+                    "Bybit error {}: {}",
+                    code, response.ret_msg
+                )))
+            }
+        }
+
+        let ticker = response
+            .result
+            .and_then(|result| result.list.into_iter().next())
+            .ok_or_else(|| {
+                FeedError::InvalidData(format!("No ticker for {} in response", self.symbol))
+            })?;
+
+        let price = Decimal::from_str(&ticker.last_price).map_err(|e| {
+            FeedError::ParseError(format!("Invalid price {}: {}", ticker.last_price, e))
+        })?;
+        if price <= Decimal::ZERO {
+            return Err(FeedError::InvalidData("Price must be positive".to_string()));
+        }
+
+        let timestamp = DateTime::from_timestamp_millis(response.time)
+            .ok_or_else(|| FeedError::ParseError(format!("Invalid timestamp {}", response.time)))?;
+
+        Ok(PriceData {
+            pair: self.pair.clone(),
+            price,
+            source: self.name().to_string(),
+            timestamp,
+            slot: None,
+            price_impact_pct: None,
+        })
+    }
+}
@@ -0,0 +1,211 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use reqwest::Client;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use std::time::Duration;
+use tokio::sync::OnceCell;
+
+use crate::config::FeedConfig;
+use crate::error::FeedError;
+use crate::models::PriceData;
+
+use super::PriceFeed;
+
+/// `latestRoundData()` selector
+const LATEST_ROUND_DATA: &str = "0xfeaf968c";
+/// `decimals()` selector
+const DECIMALS: &str = "0x313ce567";
+
+/// ABI word size in bytes
+const WORD: usize = 32;
+
+/// Chainlink-style aggregator answer read with JSON-RPC `eth_call`
+pub struct ChainlinkFeed {
+    client: Client,
+    pair: String,
+    priority: u32,
+    rpc_url: String,
+    aggregator: String,
+    staleness_threshold: chrono::Duration,
+    timeout: Duration,
+    /// Answer decimals; fixed per aggregator, so read once
+    decimals: OnceCell<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcResponse {
+    /// Hex-encoded return data
+    result: Option<String>,
+    error: Option<RpcError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl ChainlinkFeed {
+    pub fn new(config: &FeedConfig, client: Client) -> Result<Self, FeedError> {
+        let settings = config
+            .chainlink
+            .as_ref()
+            .ok_or_else(|| FeedError::Config("missing [feeds.chainlink] section".to_string()))?;
+
+        Ok(Self {
+            client,
+            pair: config.pair(),
+            priority: config.priority,
+            rpc_url: settings.rpc_url.clone(),
+            aggregator: settings.aggregator.clone(),
+            staleness_threshold: chrono::Duration::seconds(
+                settings.staleness_threshold_secs as i64,
+            ),
+            timeout: Duration::from_millis(settings.timeout_ms),
+            decimals: OnceCell::new(),
+        })
+    }
+
+    /// Call a no-argument view function and return its ABI-encoded result
+    async fn eth_call(&self, selector: &str) -> Result<Vec<u8>, FeedError> {
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "eth_call",
+            "params": [{"to": self.aggregator, "data": selector}, "latest"],
+        });
+
+        let response: RpcResponse = self
+            .client
+            .post(&self.rpc_url)
+            .json(&request)
+            .timeout(self.timeout)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        if let Some(error) = response.error {
+            return Err(FeedError::InvalidData(format!(
+                "RPC error {}: {}",
+                error.code, error.message
+            )));
+        }
+        let result = response
+            .result
+            .ok_or_else(|| FeedError::ParseError("RPC response without result".to_string()))?;
+
+        let data = hex::decode(result.trim_start_matches("0x"))
+            .map_err(|e| FeedError::ParseError(format!("Invalid call result: {}", e)))?;
+        // Calls to an address without code succeed with empty return data
+        if data.is_empty() {
+            return Err(FeedError::Config(format!(
+                "no aggregator contract at {}",
+                self.aggregator
+            )));
+        }
+        Ok(data)
+    }
+
+    async fn decimals(&self) -> Result<u32, FeedError> {
+        self.decimals
+            .get_or_try_init(|| async {
+                let data = self.eth_call(DECIMALS).await?;
+                let decimals = read_uint(&data, 0)?;
+                u32::try_from(decimals)
+                    .ok()
+                    .filter(|decimals| *decimals <= 28)
+                    .ok_or_else(|| {
+                        FeedError::InvalidData(format!("Unsupported decimals {}", decimals))
+                    })
+            })
+            .await
+            .copied()
+    }
+}
+
+/// Word `index` of ABI-encoded data as an unsigned integer; values above
+/// `u128` are rejected rather than truncated
+fn read_uint(data: &[u8], index: usize) -> Result<u128, FeedError> {
+    let word = data.get(index * WORD..(index + 1) * WORD).ok_or_else(|| {
+        FeedError::ParseError(format!(
+            "Call result too short: {} bytes, need {}",
+            data.len(),
+            (index + 1) * WORD
+        ))
+    })?;
+
+    let (high, low) = word.split_at(WORD / 2);
+    if high.iter().any(|byte| *byte != 0) {
+        return Err(FeedError::InvalidData(format!(
+            "Word {} out of range: 0x{}",
+            index,
+            hex::encode(word)
+        )));
+    }
+    Ok(u128::from_be_bytes(low.try_into().expect("16 bytes")))
+}
+
+#[async_trait]
+impl PriceFeed for ChainlinkFeed {
+    fn name(&self) -> &str {
+        "chainlink"
+    }
+
+    fn pair(&self) -> &str {
+        &self.pair
+    }
+
+    fn priority(&self) -> u32 {
+        self.priority
+    }
+
+    async fn fetch_price(&self) -> Result<PriceData, FeedError> {
+        let decimals = self.decimals().await?;
+        // (roundId, answer, startedAt, updatedAt, answeredInRound)
+        let data = self.eth_call(LATEST_ROUND_DATA).await?;
+        let round_id = read_uint(&data, 0)?;
+        // int256; a negative answer fails the range check like an oversized one
+        let answer = read_uint(&data, 1)?;
+        let updated_at = read_uint(&data, 3)?;
+
+        if updated_at == 0 {
+            return Err(FeedError::InvalidData(format!(
+                "Round {} is not complete",
+                round_id
+            )));
+        }
+        let timestamp = i64::try_from(updated_at)
+            .ok()
+            .and_then(|secs| DateTime::from_timestamp(secs, 0))
+            .ok_or_else(|| FeedError::ParseError(format!("Invalid updatedAt {}", updated_at)))?;
+
+        if Utc::now() - timestamp > self.staleness_threshold {
+            return Err(FeedError::InvalidData(format!(
+                "Round {} updated at {} is older than {}s",
+                round_id,
+                timestamp,
+                self.staleness_threshold.num_seconds()
+            )));
+        }
+
+        let price = i128::try_from(answer)
+            .ok()
+            .and_then(|answer| Decimal::try_from_i128_with_scale(answer, decimals).ok())
+            .ok_or_else(|| FeedError::InvalidData(format!("Answer {} out of range", answer)))?;
+        if price <= Decimal::ZERO {
+            return Err(FeedError::InvalidData("Price must be positive".to_string()));
+        }
+
+        Ok(PriceData {
+            pair: self.pair.clone(),
+            price,
+            source: self.name().to_string(),
+            timestamp,
+            slot: None,
+            price_impact_pct: None,
+        })
+    }
+}
@@ -0,0 +1,247 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use reqwest::Client;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::config::{FeedConfig, FeedMode};
+use crate::error::FeedError;
+use crate::models::PriceData;
+
+use super::stream::{StreamProtocol, TickerStream};
+use super::PriceFeed;
+
+/// Coinbase Exchange price feed, polling `/products/{id}/ticker` or following
+/// the `ticker` WebSocket channel depending on `[feeds.coinbase] mode`
+pub struct CoinbaseFeed {
+    client: Client,
+    pair: String,
+    priority: u32,
+    ticker_url: String,
+    timeout: Duration,
+    /// Set in WebSocket mode
+    stream: Option<TickerStream>,
+}
+
+/// REST ticker response; only the fields we use
+#[derive(Debug, Deserialize)]
+struct TickerResponse {
+    price: String,
+    time: DateTime<Utc>,
+}
+
+/// WebSocket frame; `type` is `ticker` for price updates
+#[derive(Debug, Deserialize)]
+struct StreamMessage {
+    #[serde(rename = "type")]
+    kind: String,
+    product_id: Option<String>,
+    price: Option<String>,
+    time: Option<DateTime<Utc>>,
+    message: Option<String>,
+    reason: Option<String>,
+}
+
+impl CoinbaseFeed {
+    pub fn new(config: &FeedConfig, client: Client) -> Self {
+        let settings = config.coinbase.clone().unwrap_or_default();
+        let product_id = settings.product_id.unwrap_or_else(|| {
+            format!(
+                "{}-{}",
+                config.base_token.to_uppercase(),
+                config.quote_token.to_uppercase()
+            )
+        });
+
+        let stream = match settings.mode {
+            FeedMode::Rest => None,
+            FeedMode::Websocket => Some(TickerStream::new(CoinbaseStream {
+                url: settings.ws_url,
+                pair: config.pair(),
+                product_id: product_id.clone(),
+            })),
+        };
+
+        Self {
+            client,
+            pair: config.pair(),
+            priority: config.priority,
+            ticker_url: format!(
+                "{}/products/{}/ticker",
+                settings.rest_url.trim_end_matches('/'),
+                product_id
+            ),
+            timeout: Duration::from_millis(settings.timeout_ms),
+            stream,
+        }
+    }
+}
+
+#[async_trait]
+impl PriceFeed for CoinbaseFeed {
+    fn name(&self) -> &str {
+        "coinbase"
+    }
+
+    fn pair(&self) -> &str {
+        &self.pair
+    }
+
+    fn priority(&self) -> u32 {
+        self.priority
+    }
+
+    async fn fetch_price(&self) -> Result<PriceData, FeedError> {
+        if let Some(stream) = &self.stream {
+            return stream.latest().await;
+        }
+
+        let ticker: TickerResponse = self
+            .client
+            .get(&self.ticker_url)
+            .timeout(self.timeout)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        Ok(PriceData {
+            pair: self.pair.clone(),
+            price: parse_price(&ticker.price)?,
+            source: self.name().to_string(),
+            timestamp: ticker.time,
+            slot: None,
+            price_impact_pct: None,
+        })
+    }
+}
+
+struct CoinbaseStream {
+    url: String,
+    pair: String,
+    product_id: String,
+}
+
+impl StreamProtocol for CoinbaseStream {
+    fn url(&self) -> &str {
+        &self.url
+    }
+
+    fn subscribe_messages(&self) -> Vec<String> {
+        vec![serde_json::json!({
+            "type": "subscribe",
+            "product_ids": [self.product_id],
+            "channels": ["ticker"],
+        })
+        .to_string()]
+    }
+
+    fn parse(&self, text: &str) -> Result<Option<PriceData>, FeedError> {
+        let message: StreamMessage =
+            serde_json::from_str(text).map_err(|e| FeedError::ParseError(e.to_string()))?;
+
+        match message.kind.as_str() {
+            "ticker" if message.product_id.as_deref() == Some(self.product_id.as_str()) => {}
+            // e.g. a rejected subscription: {"type":"error","message":"Failed to subscribe","reason":"..."}
+            "error" => {
+                let detail: Vec<String> = [message.message, message.reason]
+                    .into_iter()
+                    .flatten()
+                    .filter(|part| !part.is_empty())
+                    .collect();
+                return Err(FeedError::Stream(if detail.is_empty() {
+                    "Coinbase reported an error".to_string()
+                } else {
+                    detail.join(": ")
+                }));
+            }
+            _ => return Ok(None),
+        }
+
+        let price = message
+            .price
+            .ok_or_else(|| FeedError::ParseError("Ticker without price".to_string()))?;
+
+        Ok(Some(PriceData {
+            pair: self.pair.clone(),
+            price: parse_price(&price)?,
+            source: "coinbase".to_string(),
+            timestamp: message.time.unwrap_or_else(Utc::now),
+            slot: None,
+            price_impact_pct: None,
+        }))
+    }
+}
+
+fn parse_price(price: &str) -> Result<Decimal, FeedError> {
+    let price = Decimal::from_str(price)
+        .map_err(|e| FeedError::ParseError(format!("Invalid price {}: {}", price, e)))?;
+
+    if price <= Decimal::ZERO {
+        return Err(FeedError::InvalidData("Price must be positive".to_string()));
+    }
+    Ok(price)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn protocol() -> CoinbaseStream {
+        CoinbaseStream {
+            url: "wss://ws-feed.exchange.coinbase.com".to_string(),
+            pair: "SOL/USD".to_string(),
+            product_id: "SOL-USD".to_string(),
+        }
+    }
+
+    #[test]
+    fn ticker_frames_carry_the_price() {
+        let tick = protocol()
+            .parse(r#"{"type":"ticker","product_id":"SOL-USD","price":"150.25","time":"2026-01-02T03:04:05.678Z"}"#)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(tick.pair, "SOL/USD");
+        assert_eq!(tick.price, Decimal::new(15025, 2));
+        assert_eq!(tick.timestamp.timestamp_millis(), 1_767_323_045_678);
+    }
+
+    #[test]
+    fn other_frames_are_skipped() {
+        let protocol = protocol();
+
+        for frame in [
+            r#"{"type":"subscriptions","channels":[{"name":"ticker","product_ids":["SOL-USD"]}]}"#,
+            r#"{"type":"heartbeat","product_id":"SOL-USD"}"#,
+            r#"{"type":"ticker","product_id":"BTC-USD","price":"90000"}"#,
+        ] {
+            assert!(protocol.parse(frame).unwrap().is_none(), "{}", frame);
+        }
+    }
+
+    #[test]
+    fn error_frames_carry_the_server_message() {
+        let error = protocol()
+            .parse(r#"{"type":"error","message":"Failed to subscribe","reason":"SOL-XYZ is not a valid product"}"#)
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            FeedError::Stream(message) if message == "Failed to subscribe: SOL-XYZ is not a valid product"
+        ));
+
+        let error = protocol().parse(r#"{"type":"error"}"#).unwrap_err();
+        assert!(matches!(error, FeedError::Stream(_)));
+    }
+
+    #[test]
+    fn malformed_frames_are_parse_errors() {
+        assert!(matches!(
+            protocol().parse("not json"),
+            Err(FeedError::ParseError(_))
+        ));
+    }
+}
@@ -0,0 +1,253 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::Client;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde_json::Value;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::config::{FeedConfig, TimestampFormat};
+use crate::error::FeedError;
+use crate::models::PriceData;
+
+use super::PriceFeed;
+
+/// Placeholder in header values replaced with the feed's API key
+const API_KEY_PLACEHOLDER: &str = "{api_key}";
+
+/// Price feed for any REST endpoint returning JSON, configured by `[feeds.http_json]`
+pub struct HttpJsonFeed {
+    client: Client,
+    /// Feed id, so several `http_json` sources can serve the same pair
+    id: String,
+    pair: String,
+    priority: u32,
+    url: String,
+    headers: HeaderMap,
+    price_pointer: String,
+    timestamp_pointer: Option<String>,
+    timestamp_format: TimestampFormat,
+    scale: Option<Decimal>,
+    invert: bool,
+    timeout: Duration,
+}
+
+impl HttpJsonFeed {
+    pub fn new(config: &FeedConfig, client: Client) -> Result<Self, FeedError> {
+        let settings = config
+            .http_json
+            .as_ref()
+            .ok_or_else(|| FeedError::Config("missing [feeds.http_json] section".to_string()))?;
+        let api_key = config.api_key()?;
+
+        let url = settings
+            .url
+            .replace("{base}", &config.base_token)
+            .replace("{quote}", &config.quote_token)
+            .replace("{base_lower}", &config.base_token.to_lowercase())
+            .replace("{quote_lower}", &config.quote_token.to_lowercase());
+
+        let mut headers = HeaderMap::new();
+        for (name, value) in &settings.headers {
+            let value = if value.contains(API_KEY_PLACEHOLDER) {
+                let api_key = api_key.as_ref().ok_or_else(|| {
+                    FeedError::Config(format!(
+                        "header {} uses {} but no api_key_env or api_key_file is set",
+                        name, API_KEY_PLACEHOLDER
+                    ))
+                })?;
+                value.replace(API_KEY_PLACEHOLDER, api_key.expose())
+            } else {
+                value.clone()
+            };
+
+            let name = HeaderName::from_bytes(name.as_bytes())
+                .map_err(|e| FeedError::Config(format!("invalid header name {}: {}", name, e)))?;
+            let mut value = HeaderValue::from_str(&value).map_err(|e| {
+                FeedError::Config(format!("invalid value for header {}: {}", name, e))
+            })?;
+            value.set_sensitive(true);
+            headers.insert(name, value);
+        }
+
+        Ok(Self {
+            client,
+            id: config.id(),
+            pair: config.pair(),
+            priority: config.priority,
+            url,
+            headers,
+            price_pointer: settings.price_pointer.clone(),
+            timestamp_pointer: settings.timestamp_pointer.clone(),
+            timestamp_format: settings.timestamp_format,
+            scale: settings.scale,
+            invert: settings.invert,
+            timeout: Duration::from_millis(settings.timeout_ms),
+        })
+    }
+
+    fn parse_timestamp(&self, body: &Value) -> Result<DateTime<Utc>, FeedError> {
+        let Some(pointer) = self.timestamp_pointer.as_deref() else {
+            return Ok(Utc::now());
+        };
+
+        let value = body
+            .pointer(pointer)
+            .ok_or_else(|| FeedError::ParseError(format!("Timestamp not found at {}", pointer)))?;
+
+        decode_timestamp(value, self.timestamp_format)?.ok_or_else(|| {
+            FeedError::ParseError(format!(
+                "Invalid {:?} timestamp at {}: {}",
+                self.timestamp_format, pointer, value
+            ))
+        })
+    }
+}
+
+/// `None` when the value is out of range or not in `format`
+fn decode_timestamp(
+    value: &Value,
+    format: TimestampFormat,
+) -> Result<Option<DateTime<Utc>>, FeedError> {
+    Ok(match format {
+        TimestampFormat::UnixSeconds => parse_decimal(value)?
+            .checked_mul(Decimal::ONE_THOUSAND)
+            .and_then(|millis| millis.to_i64())
+            .and_then(DateTime::from_timestamp_millis),
+        TimestampFormat::UnixMillis => parse_decimal(value)?
+            .to_i64()
+            .and_then(DateTime::from_timestamp_millis),
+        TimestampFormat::Rfc3339 => value
+            .as_str()
+            .and_then(|text| DateTime::parse_from_rfc3339(text).ok())
+            .map(|timestamp| timestamp.with_timezone(&Utc)),
+    })
+}
+
+/// Accept JSON numbers and numeric strings, including scientific notation
+fn parse_decimal(value: &Value) -> Result<Decimal, FeedError> {
+    let text = match value {
+        Value::Number(number) => number.to_string(),
+        Value::String(text) => text.trim().to_string(),
+        other => {
+            return Err(FeedError::ParseError(format!(
+                "Expected a number, got {}",
+                other
+            )))
+        }
+    };
+
+    Decimal::from_str(&text)
+        .or_else(|_| Decimal::from_scientific(&text))
+        .map_err(|e| FeedError::ParseError(format!("Invalid number {}: {}", text, e)))
+}
+
+#[async_trait]
+impl PriceFeed for HttpJsonFeed {
+    fn name(&self) -> &str {
+        &self.id
+    }
+
+    fn pair(&self) -> &str {
+        &self.pair
+    }
+
+    fn priority(&self) -> u32 {
+        self.priority
+    }
+
+    async fn fetch_price(&self) -> Result<PriceData, FeedError> {
+        let body: Value = self
+            .client
+            .get(&self.url)
+            .headers(self.headers.clone())
+            .timeout(self.timeout)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        let raw = body.pointer(&self.price_pointer).ok_or_else(|| {
+            FeedError::ParseError(format!("Price not found at {}", self.price_pointer))
+        })?;
+
+        let mut price = parse_decimal(raw)?;
+        if let Some(scale) = self.scale {
+            price = price.checked_mul(scale).ok_or_else(|| {
+                FeedError::InvalidData(format!("Price {} scaled by {} overflows", price, scale))
+            })?;
+        }
+
+        if price <= Decimal::ZERO {
+            return Err(FeedError::InvalidData("Price must be positive".to_string()));
+        }
+
+        if self.invert {
+            price = Decimal::ONE.checked_div(price).ok_or_else(|| {
+                FeedError::InvalidData(format!("Inverse of price {} overflows", price))
+            })?;
+        }
+
+        Ok(PriceData {
+            pair: self.pair.clone(),
+            price,
+            source: self.name().to_string(),
+            timestamp: self.parse_timestamp(&body)?,
+            slot: None,
+            price_impact_pct: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn expected() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2024-06-01T12:00:00.500Z")
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn decodes_unix_seconds() {
+        for value in [json!(1717243200.5), json!("1717243200.5")] {
+            let timestamp = decode_timestamp(&value, TimestampFormat::UnixSeconds).unwrap();
+            assert_eq!(timestamp, Some(expected()));
+        }
+    }
+
+    #[test]
+    fn decodes_unix_millis() {
+        let timestamp =
+            decode_timestamp(&json!(1717243200500u64), TimestampFormat::UnixMillis).unwrap();
+        assert_eq!(timestamp, Some(expected()));
+    }
+
+    #[test]
+    fn decodes_rfc3339() {
+        let value = json!("2024-06-01T14:00:00.500+02:00");
+        let timestamp = decode_timestamp(&value, TimestampFormat::Rfc3339).unwrap();
+        assert_eq!(timestamp, Some(expected()));
+
+        let number = decode_timestamp(&json!(1717243200), TimestampFormat::Rfc3339).unwrap();
+        assert_eq!(number, None);
+    }
+
+    #[test]
+    fn out_of_range_timestamps_are_rejected_without_panicking() {
+        for format in [TimestampFormat::UnixSeconds, TimestampFormat::UnixMillis] {
+            let timestamp = decode_timestamp(&json!(1e26), format).unwrap();
+            assert_eq!(timestamp, None);
+        }
+        let near_max = json!("79000000000000000000000000000");
+        assert_eq!(
+            decode_timestamp(&near_max, TimestampFormat::UnixSeconds).unwrap(),
+            None
+        );
+    }
+}
@@ -5,7 +5,7 @@ use rust_decimal::Decimal;
 use serde::Deserialize;
 use std::collections::HashMap;
 
-use crate::config::FeedConfig;
+use crate::config::{FeedConfig, Secret};
 use crate::error::FeedError;
 use crate::models::PriceData;
 
@@ -25,7 +25,7 @@ pub struct JupiterFeed {
     #[allow(dead_code)]
     quote_mint: String,
     priority: u32,
-    api_key: Option<String>,
+    api_key: Option<Secret>,
 }
 
 /// Jupiter v3 API response format:
@@ -39,23 +39,27 @@ struct JupiterPriceData {
 type JupiterResponse = HashMap<String, JupiterPriceData>;
 
 impl JupiterFeed {
-    pub fn new(config: &FeedConfig, client: Client) -> Self {
+    pub fn new(config: &FeedConfig, client: Client) -> Result<Self, FeedError> {
         let base_mint = token_to_mint(&config.base_token);
         let quote_mint = token_to_mint(&config.quote_token);
-        let api_key = std::env::var("JUPITER_API_KEY").ok();
+        // `JUPITER_API_KEY` is still honoured when the feed references no secret
+        let api_key = match config.api_key()? {
+            Some(key) => Some(key),
+            None => std::env::var("JUPITER_API_KEY").ok().map(Secret::new),
+        };
 
-        Self {
+        Ok(Self {
             client,
             pair: config.pair(),
             base_mint,
             quote_mint,
             priority: config.priority,
             api_key,
-        }
+        })
     }
 }
 
-fn token_to_mint(token: &str) -> String {
+pub(super) fn token_to_mint(token: &str) -> String {
     match token.to_uppercase().as_str() {
         "SOL" => SOL_MINT.to_string(),
         "USDC" => USDC_MINT.to_string(),
@@ -64,6 +68,15 @@ fn token_to_mint(token: &str) -> String {
     }
 }
 
+/// Decimals of the tokens `token_to_mint` knows by symbol
+pub(super) fn token_decimals(token: &str) -> Option<u8> {
+    match token.to_uppercase().as_str() {
+        "SOL" => Some(9),
+        "USDC" | "USDT" => Some(6),
+        _ => None,
+    }
+}
+
 #[async_trait]
 impl PriceFeed for JupiterFeed {
     fn name(&self) -> &str {
@@ -88,7 +101,7 @@ impl PriceFeed for JupiterFeed {
             .timeout(std::time::Duration::from_secs(5));
 
         if let Some(ref api_key) = self.api_key {
-            request = request.header("x-api-key", api_key);
+            request = request.header("x-api-key", api_key.expose());
         }
 
         let response: JupiterResponse = request.send().await?.json().await?;
@@ -109,6 +122,8 @@ impl PriceFeed for JupiterFeed {
             price,
             source: self.name().to_string(),
             timestamp: Utc::now(),
+            slot: None,
+            price_impact_pct: None,
         })
     }
 }
@@ -0,0 +1,214 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use reqwest::Client;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::config::{FeedConfig, QuoteSide, Secret};
+use crate::error::FeedError;
+use crate::models::PriceData;
+
+use super::jupiter::{token_decimals, token_to_mint};
+use super::PriceFeed;
+
+/// Effective execution price of a fixed-size swap, from the Jupiter `/quote` endpoint
+pub struct JupiterQuoteFeed {
+    client: Client,
+    pair: String,
+    priority: u32,
+    quote_url: String,
+    input_mint: String,
+    output_mint: String,
+    /// `amount` in the input token's smallest unit
+    in_amount: u64,
+    input_decimals: u8,
+    output_decimals: u8,
+    side: QuoteSide,
+    api_key: Option<Secret>,
+    timeout: Duration,
+}
+
+/// Only the fields we use; amounts are raw integer strings
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct QuoteResponse {
+    in_amount: String,
+    out_amount: String,
+    /// A fraction, e.g. `"0.0012"` for 0.12%
+    price_impact_pct: String,
+    context_slot: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct QuoteError {
+    error: String,
+}
+
+impl JupiterQuoteFeed {
+    pub fn new(config: &FeedConfig, client: Client) -> Result<Self, FeedError> {
+        let settings = config.jupiter_quote.as_ref().ok_or_else(|| {
+            FeedError::Config("missing [feeds.jupiter_quote] section".to_string())
+        })?;
+
+        let decimals = |token: &str, configured: Option<u8>, field: &str| {
+            configured.or_else(|| token_decimals(token)).ok_or_else(|| {
+                FeedError::Config(format!("{} is required for token {}", field, token))
+            })
+        };
+        let base_decimals = decimals(&config.base_token, settings.base_decimals, "base_decimals")?;
+        let quote_decimals = decimals(
+            &config.quote_token,
+            settings.quote_decimals,
+            "quote_decimals",
+        )?;
+
+        let base_mint = token_to_mint(&config.base_token);
+        let quote_mint = token_to_mint(&config.quote_token);
+        let (input_mint, output_mint, input_decimals, output_decimals) = match settings.side {
+            QuoteSide::Sell => (base_mint, quote_mint, base_decimals, quote_decimals),
+            QuoteSide::Buy => (quote_mint, base_mint, quote_decimals, base_decimals),
+        };
+
+        let scaled = settings
+            .amount
+            .checked_mul(pow10(input_decimals)?)
+            .ok_or_else(|| {
+                FeedError::Config(format!("amount {} is out of range", settings.amount))
+            })?;
+        let in_amount = scaled
+            .to_u64()
+            .filter(|_| scaled.fract().is_zero())
+            .ok_or_else(|| {
+                FeedError::Config(format!(
+                    "amount {} is not a whole number of input token units",
+                    settings.amount
+                ))
+            })?;
+
+        // `JUPITER_API_KEY` is honoured as for the `jupiter` feed
+        let api_key = match config.api_key()? {
+            Some(key) => Some(key),
+            None => std::env::var("JUPITER_API_KEY").ok().map(Secret::new),
+        };
+
+        Ok(Self {
+            client,
+            pair: config.pair(),
+            priority: config.priority,
+            quote_url: format!("{}/quote", settings.api_url.trim_end_matches('/')),
+            input_mint,
+            output_mint,
+            in_amount,
+            input_decimals,
+            output_decimals,
+            side: settings.side,
+            api_key,
+            timeout: Duration::from_millis(settings.timeout_ms),
+        })
+    }
+}
+
+fn pow10(decimals: u8) -> Result<Decimal, FeedError> {
+    10u64
+        .checked_pow(u32::from(decimals))
+        .map(Decimal::from)
+        .ok_or_else(|| FeedError::Config(format!("unsupported token decimals {}", decimals)))
+}
+
+fn parse_amount(raw: &str, decimals: u8) -> Result<Decimal, FeedError> {
+    let amount = Decimal::from_str(raw)
+        .map_err(|e| FeedError::ParseError(format!("Invalid amount {}: {}", raw, e)))?;
+    Ok(amount / pow10(decimals)?)
+}
+
+#[async_trait]
+impl PriceFeed for JupiterQuoteFeed {
+    fn name(&self) -> &str {
+        "jupiter_quote"
+    }
+
+    fn pair(&self) -> &str {
+        &self.pair
+    }
+
+    fn priority(&self) -> u32 {
+        self.priority
+    }
+
+    async fn fetch_price(&self) -> Result<PriceData, FeedError> {
+        let mut request = self
+            .client
+            .get(&self.quote_url)
+            .query(&[
+                ("inputMint", self.input_mint.as_str()),
+                ("outputMint", self.output_mint.as_str()),
+                ("amount", &self.in_amount.to_string()),
+            ])
+            .timeout(self.timeout);
+
+        if let Some(ref api_key) = self.api_key {
+            request = request.header("x-api-key", api_key.expose());
+        }
+
+        let response = request.send().await?;
+        // No route or an unknown mint come back as 4xx with an `error` message
+        if response.status().is_client_error() {
+            let status = response.status();
+            let message = match response.json::<QuoteError>().await {
+                Ok(body) => body.error,
+                Err(_) => status.to_string(),
+            };
+            return Err(FeedError::InvalidData(format!(
+                "Jupiter quote failed: {}",
+                message
+            )));
+        }
+        let quote: QuoteResponse = response.error_for_status()?.json().await?;
+
+        let input = parse_amount(&quote.in_amount, self.input_decimals)?;
+        let output = parse_amount(&quote.out_amount, self.output_decimals)?;
+        if input <= Decimal::ZERO || output <= Decimal::ZERO {
+            return Err(FeedError::InvalidData(format!(
+                "Empty quote: {} in, {} out",
+                quote.in_amount, quote.out_amount
+            )));
+        }
+
+        // Always quote per base token, whichever way the swap goes
+        let price = match self.side {
+            QuoteSide::Sell => output.checked_div(input),
+            QuoteSide::Buy => input.checked_div(output),
+        }
+        .ok_or_else(|| {
+            FeedError::InvalidData(format!(
+                "Quote price out of range: {} in, {} out",
+                quote.in_amount, quote.out_amount
+            ))
+        })?;
+
+        let impact = Decimal::from_str(&quote.price_impact_pct).map_err(|e| {
+            FeedError::ParseError(format!(
+                "Invalid price impact {}: {}",
+                quote.price_impact_pct, e
+            ))
+        })?;
+        let impact = impact.checked_mul(Decimal::ONE_HUNDRED).ok_or_else(|| {
+            FeedError::InvalidData(format!(
+                "Price impact {} out of range",
+                quote.price_impact_pct
+            ))
+        })?;
+
+        Ok(PriceData {
+            pair: self.pair.clone(),
+            price,
+            source: self.name().to_string(),
+            timestamp: Utc::now(),
+            slot: quote.context_slot,
+            price_impact_pct: Some(impact),
+        })
+    }
+}
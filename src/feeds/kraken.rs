@@ -0,0 +1,201 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use reqwest::Client;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::config::FeedConfig;
+use crate::error::FeedError;
+use crate::models::PriceData;
+
+use super::PriceFeed;
+
+/// Kraken price feed using the public `Ticker` endpoint; serves the bid/ask mid price
+pub struct KrakenFeed {
+    client: Client,
+    pair: String,
+    priority: u32,
+    /// Kraken pair name sent as `?pair=`, e.g. `XBTUSD`
+    kraken_pair: String,
+    ticker_url: String,
+    timeout: Duration,
+}
+
+/// Every Kraken response is wrapped in `error` and `result`
+#[derive(Debug, Deserialize)]
+struct KrakenResponse {
+    #[serde(default)]
+    error: Vec<String>,
+    /// Keyed by Kraken's internal pair name, which may differ from the
+    /// requested one (`XBTUSD` comes back as `XXBTZUSD`)
+    result: Option<HashMap<String, TickerInfo>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TickerInfo {
+    /// Best ask as `[price, whole lot volume, lot volume]`
+    a: Vec<String>,
+    /// Best bid, same layout as `a`
+    b: Vec<String>,
+}
+
+impl KrakenFeed {
+    pub fn new(config: &FeedConfig, client: Client) -> Self {
+        let settings = config.kraken.clone().unwrap_or_default();
+        let kraken_pair = settings.pair.unwrap_or_else(|| {
+            format!(
+                "{}{}",
+                kraken_asset(&config.base_token),
+                kraken_asset(&config.quote_token)
+            )
+        });
+
+        Self {
+            client,
+            pair: config.pair(),
+            priority: config.priority,
+            kraken_pair,
+            ticker_url: format!(
+                "{}/0/public/Ticker",
+                settings.rest_url.trim_end_matches('/')
+            ),
+            timeout: Duration::from_millis(settings.timeout_ms),
+        }
+    }
+}
+
+/// Kraken's names for assets whose common symbol it doesn't use
+fn kraken_asset(symbol: &str) -> String {
+    match symbol.to_uppercase().as_str() {
+        "BTC" => "XBT".to_string(),
+        "DOGE" => "XDG".to_string(),
+        other => other.to_string(),
+    }
+}
+
+fn parse_level(level: &[String], side: &str) -> Result<Decimal, FeedError> {
+    let price = level
+        .first()
+        .ok_or_else(|| FeedError::ParseError(format!("Missing best {} price", side)))?;
+
+    Decimal::from_str(price)
+        .map_err(|e| FeedError::ParseError(format!("Invalid {} price {}: {}", side, price, e)))
+}
+
+#[async_trait]
+impl PriceFeed for KrakenFeed {
+    fn name(&self) -> &str {
+        "kraken"
+    }
+
+    fn pair(&self) -> &str {
+        &self.pair
+    }
+
+    fn priority(&self) -> u32 {
+        self.priority
+    }
+
+    async fn fetch_price(&self) -> Result<PriceData, FeedError> {
+        let response: KrakenResponse = self
+            .client
+            .get(&self.ticker_url)
+            .query(&[("pair", &self.kraken_pair)])
+            .timeout(self.timeout)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        if !response.error.is_empty() {
+            return Err(FeedError::InvalidData(response.error.join(", ")));
+        }
+
+        let mut result = response.result.unwrap_or_default();
+        // A single-pair request returns one entry, whatever Kraken names it
+        let ticker = match result.remove(&self.kraken_pair) {
+            Some(ticker) => ticker,
+            None if result.len() == 1 => result.into_values().next().expect("one entry"),
+            None => {
+                return Err(FeedError::InvalidData(format!(
+                    "No ticker for {} in response",
+                    self.kraken_pair
+                )))
+            }
+        };
+
+        let ask = parse_level(&ticker.a, "ask")?;
+        let bid = parse_level(&ticker.b, "bid")?;
+        if bid <= Decimal::ZERO || ask < bid {
+            return Err(FeedError::InvalidData(format!(
+                "Invalid book: bid {} ask {}",
+                bid, ask
+            )));
+        }
+
+        let price = bid
+            .checked_add(ask)
+            .map(|sum| sum / Decimal::TWO)
+            .ok_or_else(|| {
+                FeedError::ParseError(format!("Mid price of bid {} ask {} overflows", bid, ask))
+            })?;
+
+        Ok(PriceData {
+            pair: self.pair.clone(),
+            price,
+            source: self.name().to_string(),
+            // The ticker carries no timestamp
+            timestamp: Utc::now(),
+            slot: None,
+            price_impact_pct: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn feed(toml: &str) -> KrakenFeed {
+        KrakenFeed::new(&toml::from_str(toml).unwrap(), Client::new())
+    }
+
+    #[test]
+    fn asset_names_follow_kraken() {
+        assert_eq!(kraken_asset("BTC"), "XBT");
+        assert_eq!(kraken_asset("btc"), "XBT");
+        assert_eq!(kraken_asset("DOGE"), "XDG");
+        assert_eq!(kraken_asset("sol"), "SOL");
+        assert_eq!(kraken_asset("USD"), "USD");
+    }
+
+    #[test]
+    fn pair_is_derived_unless_configured() {
+        let derived = feed("type = \"kraken\"\nbase_token = \"btc\"\nquote_token = \"USD\"\n");
+        assert_eq!(derived.kraken_pair, "XBTUSD");
+        assert_eq!(derived.ticker_url, "https://api.kraken.com/0/public/Ticker");
+
+        let configured = feed(
+            "type = \"kraken\"\nbase_token = \"BTC\"\nquote_token = \"USD\"\n\n[kraken]\npair = \"XXBTZUSD\"\nrest_url = \"http://127.0.0.1:18100/\"\n",
+        );
+        assert_eq!(configured.kraken_pair, "XXBTZUSD");
+        assert_eq!(
+            configured.ticker_url,
+            "http://127.0.0.1:18100/0/public/Ticker"
+        );
+    }
+
+    #[test]
+    fn levels_parse_the_best_price() {
+        let level = vec!["150.25".to_string(), "12".to_string(), "12.000".to_string()];
+        assert_eq!(parse_level(&level, "ask").unwrap(), Decimal::new(15025, 2));
+        assert!(matches!(
+            parse_level(&[], "bid"),
+            Err(FeedError::ParseError(_))
+        ));
+    }
+}
@@ -66,6 +66,8 @@ impl PriceFeed for MockFeed {
             price,
             source: self.name().to_string(),
             timestamp: Utc::now(),
+            slot: None,
+            price_impact_pct: None,
         })
     }
 }
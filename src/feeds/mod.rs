@@ -1,12 +1,29 @@
 mod binance;
+mod bybit;
+mod chainlink;
+mod coinbase;
+mod http_json;
 mod jupiter;
+mod jupiter_quote;
+mod kraken;
 mod mock;
+mod okx;
 mod pyth;
+mod solana_pool;
+mod stream;
 
 pub use binance::BinanceFeed;
+pub use bybit::BybitFeed;
+pub use chainlink::ChainlinkFeed;
+pub use coinbase::CoinbaseFeed;
+pub use http_json::HttpJsonFeed;
 pub use jupiter::JupiterFeed;
+pub use jupiter_quote::JupiterQuoteFeed;
+pub use kraken::KrakenFeed;
 pub use mock::MockFeed;
+pub use okx::OkxFeed;
 pub use pyth::PythFeed;
+pub use solana_pool::SolanaPoolFeed;
 
 use async_trait::async_trait;
 use reqwest::Client;
@@ -30,15 +47,39 @@ pub trait PriceFeed: Send + Sync {
     async fn fetch_price(&self) -> Result<PriceData, FeedError>;
 }
 
+/// Every `type` accepted by `create_feed`
+pub const FEED_TYPES: &[&str] = &[
+    "jupiter",
+    "jupiter_quote",
+    "pyth",
+    "binance",
+    "coinbase",
+    "kraken",
+    "okx",
+    "bybit",
+    "solana_pool",
+    "chainlink",
+    "http_json",
+    "mock",
+];
+
 /// Create a price feed from configuration
 pub fn create_feed(
     config: &FeedConfig,
     http_client: Client,
 ) -> Result<Box<dyn PriceFeed>, FeedError> {
     match config.feed_type.as_str() {
-        "jupiter" => Ok(Box::new(JupiterFeed::new(config, http_client))),
+        "jupiter" => Ok(Box::new(JupiterFeed::new(config, http_client)?)),
+        "jupiter_quote" => Ok(Box::new(JupiterQuoteFeed::new(config, http_client)?)),
         "pyth" => Ok(Box::new(PythFeed::new(config, http_client))),
         "binance" => Ok(Box::new(BinanceFeed::new(config, http_client))),
+        "coinbase" => Ok(Box::new(CoinbaseFeed::new(config, http_client))),
+        "kraken" => Ok(Box::new(KrakenFeed::new(config, http_client))),
+        "okx" => Ok(Box::new(OkxFeed::new(config, http_client))),
+        "bybit" => Ok(Box::new(BybitFeed::new(config, http_client))),
+        "solana_pool" => Ok(Box::new(SolanaPoolFeed::new(config, http_client)?)),
+        "chainlink" => Ok(Box::new(ChainlinkFeed::new(config, http_client)?)),
+        "http_json" => Ok(Box::new(HttpJsonFeed::new(config, http_client)?)),
         "mock" => Ok(Box::new(MockFeed::new(config, http_client))),
         other => Err(FeedError::NotImplemented(format!(
             "Unknown feed type: {}",
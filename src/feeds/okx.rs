@@ -0,0 +1,138 @@
+use async_trait::async_trait;
+use chrono::DateTime;
+use reqwest::Client;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::config::FeedConfig;
+use crate::error::FeedError;
+use crate::models::PriceData;
+
+use super::PriceFeed;
+
+/// OKX error code for an unknown `instId`
+const INSTRUMENT_NOT_FOUND: &str = "51001";
+
+/// OKX spot price feed using `/api/v5/market/ticker`
+pub struct OkxFeed {
+    client: Client,
+    pair: String,
+    priority: u32,
+    inst_id: String,
+    ticker_url: String,
+    timeout: Duration,
+}
+
+/// OKX response envelope; `code` is `"0"` on success
+#[derive(Debug, Deserialize)]
+struct OkxResponse {
+    code: String,
+    #[serde(default)]
+    msg: String,
+    #[serde(default)]
+    data: Vec<OkxTicker>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OkxTicker {
+    last: String,
+    /// Milliseconds since the epoch, as a string
+    ts: String,
+}
+
+impl OkxFeed {
+    pub fn new(config: &FeedConfig, client: Client) -> Self {
+        let settings = config.okx.clone().unwrap_or_default();
+        let inst_id = settings.inst_id.unwrap_or_else(|| {
+            format!(
+                "{}-{}",
+                config.base_token.to_uppercase(),
+                config.quote_token.to_uppercase()
+            )
+        });
+
+        Self {
+            client,
+            pair: config.pair(),
+            priority: config.priority,
+            inst_id,
+            ticker_url: format!(
+                "{}/api/v5/market/ticker",
+                settings.rest_url.trim_end_matches('/')
+            ),
+            timeout: Duration::from_millis(settings.timeout_ms),
+        }
+    }
+}
+
+#[async_trait]
+impl PriceFeed for OkxFeed {
+    fn name(&self) -> &str {
+        "okx"
+    }
+
+    fn pair(&self) -> &str {
+        &self.pair
+    }
+
+    fn priority(&self) -> u32 {
+        self.priority
+    }
+
+    async fn fetch_price(&self) -> Result<PriceData, FeedError> {
+        // Errors come back as 4xx with the envelope, so read it before the status
+        let response: OkxResponse = self
+            .client
+            .get(&self.ticker_url)
+            .query(&[("instId", &self.inst_id)])
+            .timeout(self.timeout)
+            .send()
+            .await?
+            .json()
+            .await?;
+
+        match response.code.as_str() {
+            "0" => {}
+            INSTRUMENT_NOT_FOUND => {
+                return Err(FeedError::Config(format!(
+                    "unknown OKX instrument {}: {}",
+                    self.inst_id, response.msg
+                )))
+            }
+            code => {
+                return Err(FeedError::InvalidData(format!(
+                    "OKX error {}: {}",
+                    code, response.msg
+                )))
+            }
+        }
+
+        let ticker = response.data.into_iter().next().ok_or_else(|| {
+            FeedError::InvalidData(format!("No ticker for {} in response", self.inst_id))
+        })?;
+
+        let price = Decimal::from_str(&ticker.last)
+            .map_err(|e| FeedError::ParseError(format!("Invalid price {}: {}", ticker.last, e)))?;
+        if price <= Decimal::ZERO {
+            return Err(FeedError::InvalidData("Price must be positive".to_string()));
+        }
+
+        let timestamp = ticker
+            .ts
+            .parse()
+            .ok()
+            .and_then(DateTime::from_timestamp_millis)
+            .ok_or_else(|| FeedError::ParseError(format!("Invalid timestamp {}", ticker.ts)))?;
+
+        Ok(PriceData {
+            pair: self.pair.clone(),
+            price,
+            source: self.name().to_string(),
+            timestamp,
+            slot: None,
+            price_impact_pct: None,
+        })
+    }
+}
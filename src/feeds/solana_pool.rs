@@ -0,0 +1,528 @@
+use async_trait::async_trait;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use chrono::Utc;
+use reqwest::Client;
+use rust_decimal::Decimal;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::time::Duration;
+use tokio::sync::OnceCell;
+
+use crate::config::{FeedConfig, PoolKind};
+use crate::error::FeedError;
+use crate::models::PriceData;
+
+use super::PriceFeed;
+
+const RAYDIUM_CPMM_PROGRAM: &str = "CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C";
+const ORCA_WHIRLPOOL_PROGRAM: &str = "whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc";
+
+/// Raydium CPMM `PoolState` offsets, including the 8-byte Anchor discriminator
+mod cpmm {
+    pub const TOKEN_0_VAULT: usize = 72;
+    pub const TOKEN_1_VAULT: usize = 104;
+    pub const MINT_0_DECIMALS: usize = 331;
+    pub const MINT_1_DECIMALS: usize = 332;
+    pub const PROTOCOL_FEES_TOKEN_0: usize = 341;
+    pub const PROTOCOL_FEES_TOKEN_1: usize = 349;
+    pub const FUND_FEES_TOKEN_0: usize = 357;
+    pub const FUND_FEES_TOKEN_1: usize = 365;
+    /// Zero on pools created before creator fees existed
+    pub const CREATOR_FEES_TOKEN_0: usize = 397;
+    pub const CREATOR_FEES_TOKEN_1: usize = 405;
+}
+
+/// Orca `Whirlpool` offsets, including the 8-byte Anchor discriminator
+mod whirlpool {
+    pub const SQRT_PRICE: usize = 65;
+    pub const TOKEN_MINT_A: usize = 101;
+    pub const TOKEN_MINT_B: usize = 181;
+}
+
+/// SPL token account `amount`
+const TOKEN_ACCOUNT_AMOUNT: usize = 64;
+/// SPL mint `decimals`
+const MINT_DECIMALS: usize = 44;
+
+/// On-chain AMM pool price read from a Solana JSON-RPC node
+pub struct SolanaPoolFeed {
+    client: Client,
+    pair: String,
+    priority: u32,
+    rpc_url: String,
+    pool: String,
+    kind: PoolKind,
+    invert: bool,
+    timeout: Duration,
+    /// Whirlpool mint decimals (A, B); they never change, so are read once
+    mint_decimals: OnceCell<(u8, u8)>,
+    /// Raydium CPMM vault addresses (token 0, token 1), fixed when the pool is created
+    cpmm_vaults: OnceCell<(String, String)>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcResponse<T> {
+    result: Option<T>,
+    error: Option<RpcError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+/// `getAccountInfo` and `getMultipleAccounts` results: the value and the slot it was read at
+#[derive(Debug, Deserialize)]
+struct RpcResult<T> {
+    context: RpcContext,
+    value: T,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcContext {
+    slot: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct AccountValue {
+    /// `[data, encoding]`
+    data: (String, String),
+    owner: String,
+}
+
+struct Account {
+    slot: u64,
+    owner: String,
+    data: Vec<u8>,
+}
+
+/// Pool price of token A in token B in whole tokens, and the slot it was read at
+struct PoolPrice {
+    price: Decimal,
+    slot: u64,
+}
+
+impl SolanaPoolFeed {
+    pub fn new(config: &FeedConfig, client: Client) -> Result<Self, FeedError> {
+        let settings = config
+            .solana_pool
+            .as_ref()
+            .ok_or_else(|| FeedError::Config("missing [feeds.solana_pool] section".to_string()))?;
+
+        Ok(Self {
+            client,
+            pair: config.pair(),
+            priority: config.priority,
+            rpc_url: settings.rpc_url.clone(),
+            pool: settings.pool.clone(),
+            kind: settings.kind,
+            invert: settings.invert,
+            timeout: Duration::from_millis(settings.timeout_ms),
+            mint_decimals: OnceCell::new(),
+            cpmm_vaults: OnceCell::new(),
+        })
+    }
+
+    async fn call<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<T, FeedError> {
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+            "params": params,
+        });
+
+        let response: RpcResponse<T> = self
+            .client
+            .post(&self.rpc_url)
+            .json(&request)
+            .timeout(self.timeout)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        if let Some(error) = response.error {
+            return Err(FeedError::InvalidData(format!(
+                "RPC error {}: {}",
+                error.code, error.message
+            )));
+        }
+        response
+            .result
+            .ok_or_else(|| FeedError::ParseError("RPC response without result".to_string()))
+    }
+
+    async fn get_account(&self, address: &str) -> Result<Account, FeedError> {
+        let result: RpcResult<Option<AccountValue>> = self
+            .call(
+                "getAccountInfo",
+                serde_json::json!([address, {"encoding": "base64", "commitment": "confirmed"}]),
+            )
+            .await?;
+
+        decode_account(address, result.value, result.context.slot)
+    }
+
+    /// Several accounts read in one request, so they all reflect the same slot
+    async fn get_accounts<const N: usize>(
+        &self,
+        addresses: [&str; N],
+    ) -> Result<[Account; N], FeedError> {
+        let result: RpcResult<Vec<Option<AccountValue>>> = self
+            .call(
+                "getMultipleAccounts",
+                serde_json::json!([addresses.as_slice(), {"encoding": "base64", "commitment": "confirmed"}]),
+            )
+            .await?;
+
+        let slot = result.context.slot;
+        let returned = result.value.len();
+        let accounts = addresses
+            .iter()
+            .zip(result.value)
+            .map(|(address, value)| decode_account(address, value, slot))
+            .collect::<Result<Vec<_>, _>>()?;
+        accounts.try_into().map_err(|_| {
+            FeedError::ParseError(format!(
+                "Expected {} accounts, RPC returned {}",
+                N, returned
+            ))
+        })
+    }
+
+    fn check_owner(&self, pool: &Account, program: &str) -> Result<(), FeedError> {
+        if pool.owner != program {
+            return Err(FeedError::Config(format!(
+                "pool {} is owned by {}, expected {}",
+                self.pool, pool.owner, program
+            )));
+        }
+        Ok(())
+    }
+
+    async fn get_pool(&self, program: &str) -> Result<Account, FeedError> {
+        let account = self.get_account(&self.pool).await?;
+        self.check_owner(&account, program)?;
+        Ok(account)
+    }
+
+    async fn raydium_cpmm_price(&self) -> Result<PoolPrice, FeedError> {
+        let (vault_0, vault_1) = self
+            .cpmm_vaults
+            .get_or_try_init(|| async {
+                let pool = self.get_pool(RAYDIUM_CPMM_PROGRAM).await?;
+                Ok::<_, FeedError>((
+                    read_pubkey(&pool.data, cpmm::TOKEN_0_VAULT)?,
+                    read_pubkey(&pool.data, cpmm::TOKEN_1_VAULT)?,
+                ))
+            })
+            .await?;
+
+        // Pool fees and vault balances must come from the same slot to be consistent
+        let [pool, vault_0, vault_1] = self.get_accounts([&self.pool, vault_0, vault_1]).await?;
+        self.check_owner(&pool, RAYDIUM_CPMM_PROGRAM)?;
+
+        Ok(PoolPrice {
+            price: cpmm_price(&pool.data, &vault_0.data, &vault_1.data)?,
+            slot: pool.slot,
+        })
+    }
+
+    async fn orca_whirlpool_price(&self) -> Result<PoolPrice, FeedError> {
+        let pool = self.get_pool(ORCA_WHIRLPOOL_PROGRAM).await?;
+        let data = &pool.data;
+
+        let decimals = *self
+            .mint_decimals
+            .get_or_try_init(|| async {
+                let mint_a = read_pubkey(data, whirlpool::TOKEN_MINT_A)?;
+                let mint_b = read_pubkey(data, whirlpool::TOKEN_MINT_B)?;
+                let (mint_a, mint_b) =
+                    tokio::try_join!(self.get_account(&mint_a), self.get_account(&mint_b))?;
+                Ok::<_, FeedError>((
+                    read_u8(&mint_a.data, MINT_DECIMALS)?,
+                    read_u8(&mint_b.data, MINT_DECIMALS)?,
+                ))
+            })
+            .await?;
+
+        Ok(PoolPrice {
+            price: whirlpool_price(data, decimals)?,
+            slot: pool.slot,
+        })
+    }
+}
+
+/// An RPC account value as raw bytes; `None` means the account does not exist
+fn decode_account(
+    address: &str,
+    value: Option<AccountValue>,
+    slot: u64,
+) -> Result<Account, FeedError> {
+    let value = value.ok_or_else(|| FeedError::Config(format!("account {} not found", address)))?;
+
+    let (data, encoding) = value.data;
+    if encoding != "base64" {
+        return Err(FeedError::ParseError(format!(
+            "Unexpected account encoding {}",
+            encoding
+        )));
+    }
+    let data = BASE64
+        .decode(data)
+        .map_err(|e| FeedError::ParseError(format!("Invalid account data: {}", e)))?;
+
+    Ok(Account {
+        slot,
+        owner: value.owner,
+        data,
+    })
+}
+
+/// Price of token 0 in token 1 from a Raydium CPMM `PoolState` and its two
+/// vault token accounts
+fn cpmm_price(pool: &[u8], vault_0: &[u8], vault_1: &[u8]) -> Result<Decimal, FeedError> {
+    // Vaults also hold fees owed to the protocol, fund and creator
+    let reserve = |vault: &[u8], fees: [usize; 3]| -> Result<u64, FeedError> {
+        let mut amount = read_u64(vault, TOKEN_ACCOUNT_AMOUNT)?;
+        for offset in fees {
+            amount = amount.saturating_sub(read_u64(pool, offset)?);
+        }
+        Ok(amount)
+    };
+    let reserve_0 = reserve(
+        vault_0,
+        [
+            cpmm::PROTOCOL_FEES_TOKEN_0,
+            cpmm::FUND_FEES_TOKEN_0,
+            cpmm::CREATOR_FEES_TOKEN_0,
+        ],
+    )?;
+    let reserve_1 = reserve(
+        vault_1,
+        [
+            cpmm::PROTOCOL_FEES_TOKEN_1,
+            cpmm::FUND_FEES_TOKEN_1,
+            cpmm::CREATOR_FEES_TOKEN_1,
+        ],
+    )?;
+    if reserve_0 == 0 || reserve_1 == 0 {
+        return Err(FeedError::InvalidData("Pool has no liquidity".to_string()));
+    }
+
+    let raw = Decimal::from(reserve_1) / Decimal::from(reserve_0);
+    let exponent = i32::from(read_u8(pool, cpmm::MINT_0_DECIMALS)?)
+        - i32::from(read_u8(pool, cpmm::MINT_1_DECIMALS)?);
+    shift_decimals(raw, exponent)
+}
+
+/// Price of token A in token B from an Orca `Whirlpool` and the mint decimals (A, B)
+fn whirlpool_price(pool: &[u8], (decimals_a, decimals_b): (u8, u8)) -> Result<Decimal, FeedError> {
+    // Q64.64 fixed point; split so the fraction keeps its precision
+    let sqrt_price = read_u128(pool, whirlpool::SQRT_PRICE)?;
+    let two_pow_64 = Decimal::from(u64::MAX) + Decimal::ONE;
+    let sqrt =
+        Decimal::from((sqrt_price >> 64) as u64) + Decimal::from(sqrt_price as u64) / two_pow_64;
+
+    let raw = sqrt
+        .checked_mul(sqrt)
+        .ok_or_else(|| FeedError::InvalidData(format!("sqrt_price {} overflows", sqrt_price)))?;
+    let exponent = i32::from(decimals_a) - i32::from(decimals_b);
+    shift_decimals(raw, exponent)
+}
+
+/// The served price: positive, and `1 / price` when the pair is the pool's reverse
+fn quoted(price: Decimal, invert: bool) -> Result<Decimal, FeedError> {
+    if price <= Decimal::ZERO {
+        return Err(FeedError::InvalidData("Price must be positive".to_string()));
+    }
+    Ok(if invert { Decimal::ONE / price } else { price })
+}
+
+/// `value * 10^exponent`
+fn shift_decimals(value: Decimal, exponent: i32) -> Result<Decimal, FeedError> {
+    let factor = 10u64
+        .checked_pow(exponent.unsigned_abs())
+        .map(Decimal::from)
+        .ok_or_else(|| FeedError::InvalidData(format!("Decimals differ by {}", exponent)))?;
+
+    let shifted = if exponent >= 0 {
+        value.checked_mul(factor)
+    } else {
+        value.checked_div(factor)
+    };
+    shifted.ok_or_else(|| FeedError::InvalidData(format!("Price {} out of range", value)))
+}
+
+fn field(data: &[u8], offset: usize, len: usize) -> Result<&[u8], FeedError> {
+    data.get(offset..offset + len).ok_or_else(|| {
+        FeedError::ParseError(format!(
+            "Account data too short: {} bytes, need {}",
+            data.len(),
+            offset + len
+        ))
+    })
+}
+
+fn read_u8(data: &[u8], offset: usize) -> Result<u8, FeedError> {
+    Ok(field(data, offset, 1)?[0])
+}
+
+fn read_u64(data: &[u8], offset: usize) -> Result<u64, FeedError> {
+    let bytes = field(data, offset, 8)?;
+    Ok(u64::from_le_bytes(bytes.try_into().expect("8 bytes")))
+}
+
+fn read_u128(data: &[u8], offset: usize) -> Result<u128, FeedError> {
+    let bytes = field(data, offset, 16)?;
+    Ok(u128::from_le_bytes(bytes.try_into().expect("16 bytes")))
+}
+
+fn read_pubkey(data: &[u8], offset: usize) -> Result<String, FeedError> {
+    Ok(bs58::encode(field(data, offset, 32)?).into_string())
+}
+
+#[async_trait]
+impl PriceFeed for SolanaPoolFeed {
+    fn name(&self) -> &str {
+        match self.kind {
+            PoolKind::RaydiumCpmm => "raydium_cpmm",
+            PoolKind::OrcaWhirlpool => "orca_whirlpool",
+        }
+    }
+
+    fn pair(&self) -> &str {
+        &self.pair
+    }
+
+    fn priority(&self) -> u32 {
+        self.priority
+    }
+
+    async fn fetch_price(&self) -> Result<PriceData, FeedError> {
+        let PoolPrice { price, slot } = match self.kind {
+            PoolKind::RaydiumCpmm => self.raydium_cpmm_price().await?,
+            PoolKind::OrcaWhirlpool => self.orca_whirlpool_price().await?,
+        };
+
+        Ok(PriceData {
+            pair: self.pair.clone(),
+            price: quoted(price, self.invert)?,
+            source: self.name().to_string(),
+            // Account reads carry a slot rather than a block time
+            timestamp: Utc::now(),
+            slot: Some(slot),
+            price_impact_pct: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Fixtures are account data in `getAccountInfo`'s base64 encoding: a SOL/USDC
+    // CPMM pool whose vaults hold 1,000 SOL and 150,000 USDC plus fees owed, and
+    // a SOL/USDC whirlpool at 250
+
+    fn fixture(encoded: &str) -> Vec<u8> {
+        BASE64.decode(encoded.trim()).expect("valid base64 fixture")
+    }
+
+    fn cpmm_pool() -> Vec<u8> {
+        fixture(include_str!("testdata/raydium_cpmm_pool.b64"))
+    }
+
+    fn cpmm_vaults() -> (Vec<u8>, Vec<u8>) {
+        (
+            fixture(include_str!("testdata/raydium_cpmm_vault_0.b64")),
+            fixture(include_str!("testdata/raydium_cpmm_vault_1.b64")),
+        )
+    }
+
+    #[test]
+    fn cpmm_price_from_reserves_net_of_fees() {
+        let (vault_0, vault_1) = cpmm_vaults();
+        let price = cpmm_price(&cpmm_pool(), &vault_0, &vault_1).unwrap();
+        assert_eq!(price, Decimal::from(150));
+    }
+
+    #[test]
+    fn cpmm_price_subtracts_every_fee_bucket() {
+        let (vault_0, vault_1) = cpmm_vaults();
+        let mut pool = cpmm_pool();
+        // Without the fees the vault balances give 150,000.4 / 1,000.002
+        for offset in [
+            cpmm::PROTOCOL_FEES_TOKEN_0,
+            cpmm::PROTOCOL_FEES_TOKEN_1,
+            cpmm::FUND_FEES_TOKEN_0,
+            cpmm::FUND_FEES_TOKEN_1,
+            cpmm::CREATOR_FEES_TOKEN_0,
+            cpmm::CREATOR_FEES_TOKEN_1,
+        ] {
+            pool[offset..offset + 8].fill(0);
+        }
+
+        let price = cpmm_price(&pool, &vault_0, &vault_1).unwrap();
+        let expected = Decimal::new(150_000_400_000, 6) / Decimal::new(1_000_002_000_000, 9);
+        assert_eq!(price, expected);
+        assert_ne!(price, Decimal::from(150));
+    }
+
+    #[test]
+    fn cpmm_price_rejects_empty_pool() {
+        let (vault_0, mut vault_1) = cpmm_vaults();
+        vault_1[TOKEN_ACCOUNT_AMOUNT..TOKEN_ACCOUNT_AMOUNT + 8].fill(0);
+        assert!(cpmm_price(&cpmm_pool(), &vault_0, &vault_1).is_err());
+    }
+
+    #[test]
+    fn cpmm_price_rejects_truncated_account() {
+        let (vault_0, vault_1) = cpmm_vaults();
+        let pool = cpmm_pool();
+        assert!(cpmm_price(&pool[..300], &vault_0, &vault_1).is_err());
+    }
+
+    #[test]
+    fn whirlpool_price_from_sqrt_price() {
+        let pool = fixture(include_str!("testdata/orca_whirlpool.b64"));
+        // sqrt_price 2^63 is 0.25 USDC base units per lamport
+        let price = whirlpool_price(&pool, (9, 6)).unwrap();
+        assert_eq!(price, Decimal::from(250));
+    }
+
+    #[test]
+    fn invert_serves_reciprocal() {
+        let price = Decimal::from(250);
+        assert_eq!(quoted(price, false).unwrap(), price);
+        assert_eq!(quoted(price, true).unwrap(), Decimal::new(4, 3));
+        assert!(quoted(Decimal::ZERO, true).is_err());
+    }
+
+    #[test]
+    fn multiple_accounts_share_the_response_slot() {
+        let result: RpcResult<Vec<Option<AccountValue>>> = serde_json::from_str(
+            r#"{"context":{"slot":312345678},"value":[
+                {"data":["AQID","base64"],"owner":"CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C"},
+                null
+            ]}"#,
+        )
+        .unwrap();
+        let mut values = result.value.into_iter();
+
+        let pool = decode_account("pool", values.next().unwrap(), result.context.slot).unwrap();
+        assert_eq!(pool.slot, 312345678);
+        assert_eq!(pool.owner, RAYDIUM_CPMM_PROGRAM);
+        assert_eq!(pool.data, [1, 2, 3]);
+
+        let missing = decode_account("vault", values.next().unwrap(), result.context.slot);
+        assert!(matches!(missing, Err(FeedError::Config(message)) if message.contains("vault")));
+    }
+}
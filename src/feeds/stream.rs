@@ -0,0 +1,151 @@
+use futures_util::{SinkExt, StreamExt};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::RwLock;
+use tokio::task::AbortHandle;
+use tokio_tungstenite::tungstenite::Message;
+use tracing::{info, warn};
+
+use crate::error::FeedError;
+use crate::models::PriceData;
+
+const INITIAL_RECONNECT_DELAY: Duration = Duration::from_secs(1);
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(30);
+
+/// Exchange-specific parts of a WebSocket ticker subscription
+pub trait StreamProtocol: Send + Sync + 'static {
+    fn url(&self) -> &str;
+
+    /// Text frames sent right after connecting
+    fn subscribe_messages(&self) -> Vec<String>;
+
+    /// Price carried by one text frame; `None` for acks, heartbeats and other pairs.
+    /// Errors reported by the server (such as a rejected subscription) are
+    /// `FeedError::Stream`; any other error is logged and the frame skipped.
+    fn parse(&self, text: &str) -> Result<Option<PriceData>, FeedError>;
+}
+
+/// What the background task last received
+#[derive(Default)]
+struct StreamState {
+    tick: Option<PriceData>,
+    /// Error frame received since the last tick
+    error: Option<String>,
+}
+
+/// Latest tick from a background WebSocket subscription. The connection is
+/// opened on the first `latest` call and closed when this is dropped.
+pub struct TickerStream {
+    protocol: Arc<dyn StreamProtocol>,
+    latest: Arc<RwLock<StreamState>>,
+    task: Mutex<Option<AbortHandle>>,
+}
+
+impl TickerStream {
+    pub fn new(protocol: impl StreamProtocol) -> Self {
+        Self {
+            protocol: Arc::new(protocol),
+            latest: Arc::new(RwLock::new(StreamState::default())),
+            task: Mutex::new(None),
+        }
+    }
+
+    /// Most recent tick; its exchange timestamp lets staleness checks catch a stalled stream.
+    /// An error frame from the server is returned instead until the next tick.
+    pub async fn latest(&self) -> Result<PriceData, FeedError> {
+        self.ensure_started();
+
+        let state = self.latest.read().await;
+        if let Some(error) = &state.error {
+            return Err(FeedError::Stream(error.clone()));
+        }
+        state.tick.clone().ok_or_else(|| {
+            FeedError::InvalidData(format!("No tick received yet from {}", self.protocol.url()))
+        })
+    }
+
+    fn ensure_started(&self) {
+        let mut task = self.task.lock().expect("ticker task lock poisoned");
+        if task.is_some() {
+            return;
+        }
+
+        let protocol = self.protocol.clone();
+        let latest = self.latest.clone();
+        let handle = tokio::spawn(async move {
+            run(protocol, latest).await;
+        });
+        *task = Some(handle.abort_handle());
+    }
+}
+
+impl Drop for TickerStream {
+    fn drop(&mut self) {
+        if let Some(task) = self.task.get_mut().ok().and_then(Option::take) {
+            task.abort();
+        }
+    }
+}
+
+/// Connect, subscribe and record ticks forever, reconnecting with backoff
+async fn run(protocol: Arc<dyn StreamProtocol>, latest: Arc<RwLock<StreamState>>) {
+    let mut delay = INITIAL_RECONNECT_DELAY;
+
+    loop {
+        match stream_ticks(protocol.as_ref(), &latest, &mut delay).await {
+            Ok(()) => warn!(url = %protocol.url(), "Ticker stream closed by server"),
+            Err(e) => warn!(url = %protocol.url(), error = %e, "Ticker stream failed"),
+        }
+
+        tokio::time::sleep(delay).await;
+        delay = (delay * 2).min(MAX_RECONNECT_DELAY);
+    }
+}
+
+async fn stream_ticks(
+    protocol: &dyn StreamProtocol,
+    latest: &RwLock<StreamState>,
+    delay: &mut Duration,
+) -> Result<(), FeedError> {
+    let (mut socket, _) = tokio_tungstenite::connect_async(protocol.url())
+        .await
+        .map_err(|e| FeedError::Stream(e.to_string()))?;
+
+    for message in protocol.subscribe_messages() {
+        socket
+            .send(Message::text(message))
+            .await
+            .map_err(|e| FeedError::Stream(e.to_string()))?;
+    }
+
+    info!(url = %protocol.url(), "Ticker stream connected");
+
+    while let Some(message) = socket.next().await {
+        let text = match message.map_err(|e| FeedError::Stream(e.to_string()))? {
+            Message::Text(text) => text,
+            Message::Close(_) => return Ok(()),
+            _ => continue,
+        };
+
+        match protocol.parse(&text) {
+            Ok(Some(tick)) => {
+                *latest.write().await = StreamState {
+                    tick: Some(tick),
+                    error: None,
+                };
+                // Only a stream that delivers ticks resets the backoff
+                *delay = INITIAL_RECONNECT_DELAY;
+            }
+            Ok(None) => {}
+            Err(FeedError::Stream(error)) => {
+                warn!(url = %protocol.url(), error = %error, "Ticker stream reported an error");
+                latest.write().await.error = Some(error);
+            }
+            Err(e) => {
+                warn!(url = %protocol.url(), error = %e, "Ignoring unparseable ticker message")
+            }
+        }
+    }
+
+    Ok(())
+}
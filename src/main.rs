@@ -1,21 +1,33 @@
+mod alerts;
 mod api;
+mod cli;
 mod config;
 mod error;
 mod feeds;
 mod metrics;
 mod models;
+mod registry;
+mod reload;
 mod scheduler;
+mod signing;
+mod sinks;
 mod state;
 
+use clap::Parser;
+use std::path::PathBuf;
 use std::sync::Arc;
-use tracing::{info, warn};
+use tracing::{error, info, warn};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
-use crate::api::create_router;
+use crate::alerts::{AlertEngine, AlertRules, WebhookNotifier};
+use crate::api::{create_router, ApiKeyAuth, ApiState};
+use crate::cli::{Cli, Command};
 use crate::config::Config;
-use crate::feeds::create_feed;
 use crate::metrics::Metrics;
-use crate::scheduler::FeedScheduler;
+use crate::registry::FeedRegistry;
+use crate::reload::ConfigReloader;
+use crate::signing::PriceSigner;
+use crate::sinks::NatsSink;
 use crate::state::AppState;
 
 #[tokio::main]
@@ -29,62 +41,142 @@ async fn main() -> anyhow::Result<()> {
             tracing_subscriber::EnvFilter::try_from_default_env()
                 .unwrap_or_else(|_| "rate_relay=info".into()),
         )
-        .with(tracing_subscriber::fmt::layer())
+        .with(tracing_subscriber::fmt::layer().with_writer(std::io::stderr))
         .init();
 
+    let cli = Cli::parse();
+    match cli.command.unwrap_or(Command::Serve) {
+        Command::Serve => serve(cli.config).await,
+        Command::CheckConfig => cli::check_config(&cli.config),
+        Command::Fetch { feed, pair } => cli::fetch(&cli.config, &feed, &pair).await,
+        Command::DumpConfig => cli::dump_config(&cli.config),
+    }
+}
+
+async fn serve(config_path: PathBuf) -> anyhow::Result<()> {
     info!("Starting Rate Relay service");
 
     // Load configuration
-    let config = Config::load("config.toml")?;
+    let config = Config::load(&config_path)?;
     info!(port = config.server.port, "Configuration loaded");
 
     // Create shared state and metrics
     let app_state = AppState::new(config.server.staleness_threshold_secs);
+    app_state.set_pair_rules(&config.pairs).await;
     let metrics = Arc::new(Metrics::new());
 
-    // Create HTTP client for all feeds
-    let http_client = reqwest::Client::new();
-
-    // Spawn feed schedulers for enabled feeds
-    let enabled_feeds: Vec<_> = config.feeds.iter().filter(|f| f.enabled).collect();
-
-    if enabled_feeds.is_empty() {
-        warn!("No feeds enabled in configuration");
-    }
-
-    for feed_config in enabled_feeds {
-        match create_feed(feed_config, http_client.clone()) {
-            Ok(feed) => {
-                let scheduler = FeedScheduler::new(
-                    feed,
-                    feed_config.interval_ms,
-                    app_state.clone(),
-                    metrics.clone(),
-                );
+    // Start sinks before the feeds so no early price changes are missed
+    if let Some(nats_config) = config.sinks.nats.as_ref().filter(|n| n.enabled) {
+        match NatsSink::connect(nats_config, metrics.clone()).await {
+            Ok(sink) => {
+                let changes = app_state.subscribe();
 
                 tokio::spawn(async move {
-                    scheduler.run().await;
+                    sink.run(changes).await;
                 });
 
-                info!(
-                    feed_type = %feed_config.feed_type,
-                    pair = %feed_config.pair(),
-                    interval_ms = %feed_config.interval_ms,
-                    "Feed scheduler started"
-                );
+                info!(url = %nats_config.url, "NATS sink started");
             }
             Err(e) => {
                 warn!(
-                    feed_type = %feed_config.feed_type,
+                    url = %nats_config.url,
                     error = %e,
-                    "Failed to create feed, skipping"
+                    "Failed to start NATS sink, skipping"
                 );
             }
         }
     }
 
+    // Create HTTP client for all feeds
+    let http_client = reqwest::Client::new();
+
+    // Load alert rules and start evaluating them against price changes
+    let alert_rules = AlertRules::default();
+    for rule in config.alerts.rules.iter().cloned() {
+        let rule_id = rule.id.clone();
+        if let Err(e) = alert_rules.add(rule).await {
+            warn!(rule_id = %rule_id, error = %e, "Invalid alert rule, skipping");
+        }
+    }
+
+    let alert_engine = AlertEngine::new(
+        &config.alerts,
+        alert_rules.clone(),
+        app_state.clone(),
+        WebhookNotifier::new(&config.alerts, http_client.clone(), metrics.clone()),
+        metrics.clone(),
+    );
+    let changes = app_state.subscribe();
+    tokio::spawn(async move {
+        alert_engine.run(changes).await;
+    });
+
+    // Track every configured feed and spawn schedulers for the enabled ones
+    if !config.feeds.iter().any(|f| f.enabled) {
+        warn!("No feeds enabled in configuration");
+    }
+
+    let mut registry = FeedRegistry::new(http_client.clone(), app_state.clone(), metrics.clone());
+    if config
+        .admin
+        .as_ref()
+        .is_some_and(|admin| admin.persist_feeds)
+    {
+        registry = registry.persist_to(config_path.clone());
+    }
+    registry.start_all(&config.feeds).await?;
+
+    // Apply feed and staleness changes from the config file without a restart
+    let reloader = ConfigReloader::new(
+        config_path,
+        config.clone(),
+        registry.clone(),
+        app_state.clone(),
+    );
+    tokio::spawn(async move {
+        if let Err(e) = reloader.run().await {
+            error!(error = %e, "Configuration watcher stopped");
+        }
+    });
+
+    // Load the attestation key; a configured but unusable key is fatal
+    let signer = match config.signing.as_ref() {
+        Some(signing) => {
+            let signer = PriceSigner::from_file(&signing.key_file)?;
+            info!(public_key = %signer.public_key_hex(), "Price signing enabled");
+            Some(Arc::new(signer))
+        }
+        None => None,
+    };
+
     // Create and start HTTP server
-    let router = create_router(app_state, metrics);
+    let router = create_router(
+        ApiState {
+            app_state,
+            metrics,
+            alert_rules,
+            registry,
+            admin_token: config
+                .admin
+                .as_ref()
+                .map(|admin| admin.token.expose().to_string()),
+            required_pairs: config
+                .health
+                .required_pairs
+                .iter()
+                .map(|pair| pair.to_uppercase())
+                .collect(),
+            token_decimals: config
+                .tokens
+                .iter()
+                .map(|(symbol, token)| (symbol.to_uppercase(), token.decimals))
+                .collect(),
+            signer,
+            api_keys: (!config.api_keys.is_empty())
+                .then(|| Arc::new(ApiKeyAuth::new(config.api_keys.clone()))),
+        },
+        &config.server.http,
+    );
 
     let addr = format!("0.0.0.0:{}", config.server.port);
     let listener = tokio::net::TcpListener::bind(&addr).await?;
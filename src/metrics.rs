@@ -1,6 +1,7 @@
-use prometheus::{self, Counter, CounterVec, Encoder, GaugeVec, Opts, Registry, TextEncoder};
+use prometheus::{
+    self, CounterVec, Encoder, GaugeVec, HistogramOpts, HistogramVec, Opts, Registry, TextEncoder,
+};
 use rust_decimal::Decimal;
-use std::sync::atomic::{AtomicU64, Ordering};
 
 use crate::error::FeedError;
 
@@ -10,8 +11,15 @@ pub struct Metrics {
     fetch_errors: CounterVec,
     last_fetch_timestamp: GaugeVec,
     current_price: GaugeVec,
-    http_requests: Counter,
-    http_request_count: AtomicU64,
+    fetch_duration: HistogramVec,
+    tick_age: GaugeVec,
+    price_impact: GaugeVec,
+    sink_publish_total: CounterVec,
+    alerts_total: CounterVec,
+    webhook_deliveries_total: CounterVec,
+    api_key_requests_total: CounterVec,
+    http_requests: CounterVec,
+    http_request_duration: HistogramVec,
 }
 
 impl Metrics {
@@ -25,7 +33,10 @@ impl Metrics {
         .unwrap();
 
         let fetch_errors = CounterVec::new(
-            Opts::new("price_fetch_errors_total", "Total number of failed price fetches"),
+            Opts::new(
+                "price_fetch_errors_total",
+                "Total number of failed price fetches",
+            ),
             &["source", "pair", "error_type"],
         )
         .unwrap();
@@ -45,7 +56,80 @@ impl Metrics {
         )
         .unwrap();
 
-        let http_requests = Counter::new("http_requests_total", "Total HTTP requests").unwrap();
+        let fetch_duration = HistogramVec::new(
+            HistogramOpts::new(
+                "price_fetch_duration_seconds",
+                "Duration of upstream price fetches in seconds",
+            ),
+            &["source", "pair"],
+        )
+        .unwrap();
+
+        let tick_age = GaugeVec::new(
+            Opts::new(
+                "price_tick_age_seconds",
+                "Age of the price at the moment it was stored",
+            ),
+            &["source", "pair"],
+        )
+        .unwrap();
+
+        let price_impact = GaugeVec::new(
+            Opts::new(
+                "price_impact_percent",
+                "Price impact of the quoted trade for execution-price feeds",
+            ),
+            &["source", "pair"],
+        )
+        .unwrap();
+
+        let sink_publish_total = CounterVec::new(
+            Opts::new(
+                "sink_publish_total",
+                "Total number of price changes published to sinks",
+            ),
+            &["sink", "result"],
+        )
+        .unwrap();
+
+        let alerts_total = CounterVec::new(
+            Opts::new("alerts_total", "Total number of alert rule triggers"),
+            &["rule", "kind", "result"],
+        )
+        .unwrap();
+
+        let webhook_deliveries_total = CounterVec::new(
+            Opts::new(
+                "alert_webhook_deliveries_total",
+                "Total number of alert webhook deliveries",
+            ),
+            &["result"],
+        )
+        .unwrap();
+
+        let api_key_requests_total = CounterVec::new(
+            Opts::new(
+                "api_key_requests_total",
+                "Total number of API requests per key and auth result",
+            ),
+            &["key", "result"],
+        )
+        .unwrap();
+
+        let http_requests = CounterVec::new(
+            Opts::new("http_requests_total", "Total HTTP requests"),
+            &["route", "method", "status"],
+        )
+        .unwrap();
+
+        let http_request_duration = HistogramVec::new(
+            HistogramOpts::new(
+                "http_request_duration_seconds",
+                "HTTP request latency in seconds",
+            ),
+            &["route", "method"],
+        )
+        .unwrap();
 
         registry.register(Box::new(fetch_total.clone())).unwrap();
         registry.register(Box::new(fetch_errors.clone())).unwrap();
@@ -53,7 +137,23 @@ impl Metrics {
             .register(Box::new(last_fetch_timestamp.clone()))
             .unwrap();
         registry.register(Box::new(current_price.clone())).unwrap();
+        registry.register(Box::new(fetch_duration.clone())).unwrap();
+        registry.register(Box::new(tick_age.clone())).unwrap();
+        registry.register(Box::new(price_impact.clone())).unwrap();
+        registry
+            .register(Box::new(sink_publish_total.clone()))
+            .unwrap();
+        registry.register(Box::new(alerts_total.clone())).unwrap();
+        registry
+            .register(Box::new(webhook_deliveries_total.clone()))
+            .unwrap();
+        registry
+            .register(Box::new(api_key_requests_total.clone()))
+            .unwrap();
         registry.register(Box::new(http_requests.clone())).unwrap();
+        registry
+            .register(Box::new(http_request_duration.clone()))
+            .unwrap();
 
         Self {
             registry,
@@ -61,8 +161,15 @@ impl Metrics {
             fetch_errors,
             last_fetch_timestamp,
             current_price,
+            fetch_duration,
+            tick_age,
+            price_impact,
+            sink_publish_total,
+            alerts_total,
+            webhook_deliveries_total,
+            api_key_requests_total,
             http_requests,
-            http_request_count: AtomicU64::new(0),
+            http_request_duration,
         }
     }
 
@@ -75,7 +182,7 @@ impl Metrics {
             .with_label_values(&[source, pair])
             .set(chrono::Utc::now().timestamp() as f64);
 
-        if let Some(price_f64) = price.to_string().parse::<f64>().ok() {
+        if let Ok(price_f64) = price.to_string().parse::<f64>() {
             self.current_price
                 .with_label_values(&[source, pair])
                 .set(price_f64);
@@ -84,10 +191,14 @@ impl Metrics {
 
     pub fn record_fetch_error(&self, source: &str, pair: &str, error: &FeedError) {
         let error_type = match error {
+            FeedError::HttpError(e) if e.is_timeout() => "timeout",
             FeedError::HttpError(_) => "http",
             FeedError::ParseError(_) => "parse",
             FeedError::InvalidData(_) => "invalid_data",
             FeedError::NotImplemented(_) => "not_implemented",
+            FeedError::Config(_) => "config",
+            FeedError::Rejected(_) => "rejected",
+            FeedError::Stream(_) => "stream",
         };
 
         self.fetch_total
@@ -99,9 +210,61 @@ impl Metrics {
             .inc();
     }
 
-    pub fn record_http_request(&self, _endpoint: &str) {
-        self.http_requests.inc();
-        self.http_request_count.fetch_add(1, Ordering::Relaxed);
+    pub fn record_fetch_duration(&self, source: &str, pair: &str, seconds: f64) {
+        self.fetch_duration
+            .with_label_values(&[source, pair])
+            .observe(seconds);
+    }
+
+    pub fn record_tick_age(&self, source: &str, pair: &str, seconds: f64) {
+        self.tick_age
+            .with_label_values(&[source, pair])
+            .set(seconds);
+    }
+
+    pub fn record_price_impact(&self, source: &str, pair: &str, percent: &Decimal) {
+        if let Ok(percent) = percent.to_string().parse::<f64>() {
+            self.price_impact
+                .with_label_values(&[source, pair])
+                .set(percent);
+        }
+    }
+
+    pub fn record_sink_publish(&self, sink: &str, success: bool) {
+        let result = if success { "success" } else { "error" };
+
+        self.sink_publish_total
+            .with_label_values(&[sink, result])
+            .inc();
+    }
+
+    pub fn record_alert(&self, rule: &str, kind: &str, result: &str) {
+        self.alerts_total
+            .with_label_values(&[rule, kind, result])
+            .inc();
+    }
+
+    pub fn record_webhook_delivery(&self, result: &str) {
+        self.webhook_deliveries_total
+            .with_label_values(&[result])
+            .inc();
+    }
+
+    pub fn record_api_key_request(&self, key: &str, result: &str) {
+        self.api_key_requests_total
+            .with_label_values(&[key, result])
+            .inc();
+    }
+
+    /// `route` must be the matched route template, never the raw request path
+    pub fn record_http_request(&self, route: &str, method: &str, status: u16, seconds: f64) {
+        self.http_requests
+            .with_label_values(&[route, method, &status.to_string()])
+            .inc();
+
+        self.http_request_duration
+            .with_label_values(&[route, method])
+            .observe(seconds);
     }
 
     pub fn encode(&self) -> String {
@@ -2,12 +2,20 @@ use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
+use crate::signing::PriceSigner;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PriceData {
     pub pair: String,
     pub price: Decimal,
     pub source: String,
     pub timestamp: DateTime<Utc>,
+    /// Solana slot the price was read at, for on-chain sources
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slot: Option<u64>,
+    /// Price impact of the quoted trade in percent, for execution-price sources
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub price_impact_pct: Option<Decimal>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -17,6 +25,13 @@ pub struct PriceResponse {
     pub source: String,
     pub fallback_used: bool,
     pub timestamp: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slot: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price_impact_pct: Option<Decimal>,
+    /// Hex ed25519 signature over `(pair, price, source, timestamp)`, see `signing`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
 }
 
 impl PriceResponse {
@@ -27,8 +42,230 @@ impl PriceResponse {
             source: data.source.clone(),
             fallback_used,
             timestamp: data.timestamp,
+            slot: data.slot,
+            price_impact_pct: data.price_impact_pct,
+            signature: None,
         }
     }
+
+    pub fn signed(mut self, signer: &PriceSigner) -> Self {
+        self.signature = Some(signer.sign(&self.pair, &self.price, &self.source, &self.timestamp));
+        self
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PublicKeyResponse {
+    pub algorithm: String,
+    pub public_key: String,
+}
+
+/// One entry of a batch price response, in request order
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum BatchPriceResult {
+    Price(PriceResponse),
+    Error(PairErrorResponse),
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PairErrorResponse {
+    pub pair: String,
+    pub error: String,
+    pub code: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchPriceResponse {
+    pub prices: Vec<BatchPriceResult>,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// One hop of a conversion path; `inverted` legs divide by the pair price
+#[derive(Debug, Clone)]
+pub struct ConversionLeg {
+    pub data: PriceData,
+    pub inverted: bool,
+}
+
+impl ConversionLeg {
+    /// Units of the leg's output token per unit of its input token
+    pub fn rate(&self) -> Option<Decimal> {
+        if self.inverted {
+            Decimal::ONE.checked_div(self.data.price)
+        } else {
+            Some(self.data.price)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ConversionLegResponse {
+    pub pair: String,
+    pub price: String,
+    pub source: String,
+    pub inverted: bool,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl ConversionLegResponse {
+    pub fn from_leg(leg: &ConversionLeg) -> Self {
+        Self {
+            pair: leg.data.pair.clone(),
+            price: leg.data.price.to_string(),
+            source: leg.data.source.clone(),
+            inverted: leg.inverted,
+            timestamp: leg.data.timestamp,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ConversionResponse {
+    pub from: String,
+    pub to: String,
+    pub amount: String,
+    pub result: String,
+    pub rate: String,
+    /// Amounts are in on-chain base units
+    pub raw: bool,
+    pub path: Vec<ConversionLegResponse>,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Latest price reported by one source for a pair, as seen by `AppState`
+#[derive(Debug, Clone)]
+pub struct SourcePrice {
+    pub data: PriceData,
+    pub priority: u32,
+    pub fresh: bool,
+    pub selected: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SourcePriceResponse {
+    pub source: String,
+    pub price: String,
+    pub timestamp: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slot: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price_impact_pct: Option<Decimal>,
+    pub age_ms: i64,
+    pub priority: u32,
+    pub fresh: bool,
+    pub selected: bool,
+}
+
+impl SourcePriceResponse {
+    pub fn from_source_price(source: &SourcePrice, now: DateTime<Utc>) -> Self {
+        Self {
+            source: source.data.source.clone(),
+            price: source.data.price.to_string(),
+            timestamp: source.data.timestamp,
+            slot: source.data.slot,
+            price_impact_pct: source.data.price_impact_pct,
+            age_ms: (now - source.data.timestamp).num_milliseconds(),
+            priority: source.priority,
+            fresh: source.fresh,
+            selected: source.selected,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SourcesResponse {
+    pub pair: String,
+    pub sources: Vec<SourcePriceResponse>,
+}
+
+/// How the served price for a pair is chosen among its sources
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AggregationMode {
+    /// Lowest priority number with fresh data wins
+    #[default]
+    Priority,
+    /// Median of all fresh sources, reported with source `median`
+    Median,
+}
+
+/// Configuration and runtime status of one feed instance
+#[derive(Debug, Clone, Serialize)]
+pub struct FeedStatus {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub feed_type: String,
+    pub pair: String,
+    pub interval_ms: u64,
+    pub priority: u32,
+    pub enabled: bool,
+    /// Paused through the admin API; not persisted
+    pub paused: bool,
+    pub last_success_at: Option<DateTime<Utc>>,
+    pub last_error_at: Option<DateTime<Utc>>,
+    pub consecutive_errors: u32,
+    pub last_error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PairSourceInfo {
+    pub feed_id: String,
+    pub source: String,
+    pub priority: u32,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PairInfo {
+    pub pair: String,
+    pub sources: Vec<PairSourceInfo>,
+    pub aggregation: AggregationMode,
+    pub staleness_threshold_secs: u64,
+    /// Fresh sources needed before the pair is served
+    pub min_sources: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PairStatus {
+    /// Served from the primary source
+    Fresh,
+    /// Served, but only from a fallback source
+    Fallback,
+    /// Prices exist but none is within the staleness threshold
+    Stale,
+    /// Fresh prices exist, but from fewer sources than the pair's `min_sources`
+    InsufficientSources,
+    /// No source has reported a price yet
+    Missing,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PairHealth {
+    pub pair: String,
+    pub status: PairStatus,
+    /// Whether this pair must be servable for the service to be ready
+    pub required: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub age_ms: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl PairHealth {
+    pub fn is_servable(&self) -> bool {
+        matches!(self.status, PairStatus::Fresh | PairStatus::Fallback)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthDetailsResponse {
+    pub status: String,
+    pub timestamp: DateTime<Utc>,
+    pub pairs: Vec<PairHealth>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -43,3 +280,12 @@ pub struct HealthResponse {
 pub struct ErrorResponse {
     pub error: String,
 }
+
+/// Emitted by `AppState` whenever the selected price for a pair changes
+/// (new price, different source, or first fresh price after a gap)
+#[derive(Debug, Clone)]
+pub struct BestPriceChange {
+    pub data: PriceData,
+    pub fallback_used: bool,
+    pub previous_source: Option<String>,
+}
@@ -0,0 +1,442 @@
+use reqwest::Client;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex as SyncMutex};
+use tokio::sync::{mpsc, oneshot, Mutex};
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+use crate::config::{self, FeedChange, FeedConfig};
+use crate::error::{FeedError, RegistryError};
+use crate::feeds::{create_feed, PriceFeed};
+use crate::metrics::Metrics;
+use crate::models::PriceData;
+use crate::scheduler::{FeedScheduler, SchedulerCommand};
+use crate::state::AppState;
+
+/// Commands queued per scheduler before senders wait
+const COMMAND_BUFFER: usize = 8;
+
+/// Owns the running `FeedScheduler` tasks, in config order
+#[derive(Clone)]
+pub struct FeedRegistry {
+    inner: Arc<Mutex<Vec<RunningFeed>>>,
+    http_client: Client,
+    state: AppState,
+    metrics: Arc<Metrics>,
+    /// Config file that `add` and `remove` write the feed list back to
+    persist_path: Option<PathBuf>,
+    /// `add` and `remove` calls that were not persisted; only touched under `inner`
+    runtime: Arc<SyncMutex<RuntimeChanges>>,
+}
+
+/// Feeds added or removed at runtime without being written to the config file,
+/// re-applied on top of the file on every reload so they are not silently undone
+#[derive(Default)]
+struct RuntimeChanges {
+    added: Vec<FeedConfig>,
+    removed: Vec<String>,
+}
+
+struct RunningFeed {
+    config: FeedConfig,
+    /// `None` for disabled feeds and optional feeds that failed to build
+    task: Option<FeedTask>,
+}
+
+struct FeedTask {
+    handle: JoinHandle<()>,
+    /// `PriceFeed::name()`, which keys this feed's prices in `AppState`
+    source: String,
+    commands: mpsc::Sender<SchedulerCommand>,
+    paused: bool,
+}
+
+/// What changed when a new feed list was applied
+#[derive(Debug, Default)]
+pub struct ReloadSummary {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub updated: Vec<String>,
+    pub unchanged: usize,
+}
+
+impl ReloadSummary {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.updated.is_empty()
+    }
+}
+
+impl FeedRegistry {
+    pub fn new(http_client: Client, state: AppState, metrics: Arc<Metrics>) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(Vec::new())),
+            http_client,
+            state,
+            metrics,
+            persist_path: None,
+            runtime: Arc::new(SyncMutex::new(RuntimeChanges::default())),
+        }
+    }
+
+    /// Write feeds added or removed at runtime back to `path`
+    pub fn persist_to(mut self, path: PathBuf) -> Self {
+        self.persist_path = Some(path);
+        self
+    }
+
+    /// Register every feed at startup. A feed that cannot be constructed fails
+    /// startup unless it is marked `optional`; nothing is spawned in that case.
+    pub async fn start_all(&self, feeds: &[FeedConfig]) -> Result<(), RegistryError> {
+        let mut prepared = Vec::new();
+        for config in feeds {
+            prepared.push((config, build_or_skip(config, &self.http_client)?));
+        }
+
+        let mut running = self.inner.lock().await;
+        for (config, (feed, error)) in prepared {
+            self.state.register_feed(config).await;
+            if let Some(error) = error {
+                self.state.record_feed_error(&config.id(), &error).await;
+            }
+            running.push(self.spawn(config, feed));
+        }
+
+        Ok(())
+    }
+
+    /// Diff `feeds` against the running set and start, stop or restart schedulers.
+    /// All new and changed feeds are built first, so a failure leaves everything as it was.
+    /// Unpersisted runtime additions and removals are applied on top of `feeds`.
+    pub async fn apply(&self, feeds: &[FeedConfig]) -> Result<ReloadSummary, RegistryError> {
+        let mut running = self.inner.lock().await;
+        let mut summary = ReloadSummary::default();
+        let feeds = &self.with_runtime_changes(feeds);
+
+        let mut prepared = Vec::new();
+        for config in feeds {
+            let id = config.id();
+            match running.iter().find(|entry| entry.config.id() == id) {
+                Some(current) if current.config == *config => summary.unchanged += 1,
+                current => {
+                    let feed = build_or_skip(config, &self.http_client)?;
+                    if current.is_some() {
+                        summary.updated.push(id);
+                    } else {
+                        summary.added.push(id);
+                    }
+                    prepared.push((config, feed));
+                }
+            }
+        }
+
+        let mut previous: Vec<RunningFeed> = std::mem::take(&mut *running);
+        for config in feeds {
+            let id = config.id();
+            let current = previous
+                .iter()
+                .position(|entry| entry.config.id() == id)
+                .map(|index| previous.remove(index));
+
+            match prepared
+                .iter()
+                .position(|(prepared, _)| prepared.id() == id)
+            {
+                Some(index) => {
+                    let (config, (feed, error)) = prepared.remove(index);
+                    if let Some(entry) = current {
+                        self.stop(entry).await;
+                    }
+
+                    self.state.register_feed(config).await;
+                    if let Some(error) = error {
+                        self.state.record_feed_error(&config.id(), &error).await;
+                    }
+                    running.push(self.spawn(config, feed));
+                }
+                None => running.extend(current),
+            }
+        }
+
+        for entry in previous {
+            summary.removed.push(entry.config.id());
+            self.stop(entry).await;
+        }
+
+        Ok(summary)
+    }
+
+    /// Start a new feed; its id must not be in use
+    pub async fn add(&self, config: FeedConfig) -> Result<(), RegistryError> {
+        let mut running = self.inner.lock().await;
+        let id = config.id();
+
+        if running.iter().any(|entry| entry.config.id() == id) {
+            return Err(RegistryError::DuplicateFeed(id));
+        }
+        // Prices are keyed by (pair, source), so a second such feed would share the first's
+        let source = config.source();
+        let pair = config.pair().to_uppercase();
+        if let Some(existing) = running.iter().find(|entry| {
+            entry.config.source() == source && entry.config.pair().to_uppercase() == pair
+        }) {
+            return Err(RegistryError::DuplicateFeed(format!(
+                "{} (same source and pair as {})",
+                id,
+                existing.config.id()
+            )));
+        }
+        let problems: Vec<String> = config
+            .validate()
+            .into_iter()
+            .map(|(field, message)| format!("{}: {}", field, message))
+            .collect();
+        if !problems.is_empty() {
+            return Err(RegistryError::InvalidFeed(problems.join("; ")));
+        }
+
+        let feed = build_feed(&config, &self.http_client)
+            .map_err(|e| RegistryError::InvalidFeed(e.to_string()))?;
+
+        self.persist(FeedChange::Added(&config))?;
+        if self.persist_path.is_none() {
+            let mut runtime = self.runtime.lock().expect("runtime changes lock");
+            runtime.added.push(config.clone());
+        }
+
+        self.state.register_feed(&config).await;
+        running.push(self.spawn(&config, feed));
+        Ok(())
+    }
+
+    /// Stop a feed and drop its prices
+    pub async fn remove(&self, id: &str) -> Result<(), RegistryError> {
+        let mut running = self.inner.lock().await;
+
+        let index = running
+            .iter()
+            .position(|entry| entry.config.id() == id)
+            .ok_or_else(|| RegistryError::FeedNotFound(id.to_string()))?;
+
+        self.persist(FeedChange::Removed(id))?;
+        if self.persist_path.is_none() {
+            let mut runtime = self.runtime.lock().expect("runtime changes lock");
+            let added = runtime.added.len();
+            runtime.added.retain(|added| added.id() != id);
+            if runtime.added.len() == added {
+                runtime.removed.push(id.to_string());
+            }
+        }
+
+        let entry = running.remove(index);
+        self.stop(entry).await;
+        Ok(())
+    }
+
+    /// Pause or resume scheduled fetches; runtime only, never persisted
+    pub async fn set_paused(&self, id: &str, paused: bool) -> Result<(), RegistryError> {
+        let mut running = self.inner.lock().await;
+        let task = find_task(&mut running, id)?;
+
+        if task.paused != paused {
+            let command = if paused {
+                SchedulerCommand::Pause
+            } else {
+                SchedulerCommand::Resume
+            };
+            task.commands
+                .send(command)
+                .await
+                .map_err(|_| RegistryError::SchedulerStopped(id.to_string()))?;
+            task.paused = paused;
+        }
+
+        self.state.set_feed_paused(id, paused).await;
+        info!(feed_id = %id, paused, "Feed pause state changed");
+        Ok(())
+    }
+
+    /// Run one fetch outside the schedule and wait for its result
+    pub async fn fetch_now(&self, id: &str) -> Result<PriceData, RegistryError> {
+        let (reply, result) = oneshot::channel();
+
+        // Release the lock before waiting on the fetch itself
+        {
+            let mut running = self.inner.lock().await;
+            let task = find_task(&mut running, id)?;
+            task.commands
+                .send(SchedulerCommand::FetchNow(reply))
+                .await
+                .map_err(|_| RegistryError::SchedulerStopped(id.to_string()))?;
+        }
+
+        let result = result
+            .await
+            .map_err(|_| RegistryError::SchedulerStopped(id.to_string()))?;
+        Ok(result?)
+    }
+
+    /// `feeds` from the config file with unpersisted runtime changes applied.
+    /// Changes the file has since caught up with are forgotten.
+    fn with_runtime_changes(&self, feeds: &[FeedConfig]) -> Vec<FeedConfig> {
+        let mut runtime = self.runtime.lock().expect("runtime changes lock");
+        let RuntimeChanges { added, removed } = &mut *runtime;
+
+        removed.retain(|id| feeds.iter().any(|feed| feed.id() == *id));
+        let kept: Vec<&FeedConfig> = feeds
+            .iter()
+            .filter(|feed| !removed.contains(&feed.id()))
+            .collect();
+        added.retain(|extra| {
+            let id = extra.id();
+            let pair = extra.pair().to_uppercase();
+            match kept.iter().find(|feed| {
+                feed.id() == id
+                    || (feed.source() == extra.source() && feed.pair().to_uppercase() == pair)
+            }) {
+                Some(feed) if feed.id() != id => {
+                    warn!(
+                        feed_id = %id,
+                        config_feed_id = %feed.id(),
+                        "Feed added at runtime replaced by a configured feed with the same source and pair"
+                    );
+                    false
+                }
+                Some(_) => false,
+                None => true,
+            }
+        });
+
+        if !added.is_empty() || !removed.is_empty() {
+            let added: Vec<String> = added.iter().map(FeedConfig::id).collect();
+            info!(
+                added = ?added,
+                removed = ?removed,
+                "Keeping feeds added or removed at runtime; set admin.persist_feeds to write them to the configuration"
+            );
+        }
+
+        kept.into_iter().chain(added.iter()).cloned().collect()
+    }
+
+    /// Write only the changed feed, so values from environment overrides stay out of the file
+    fn persist(&self, change: FeedChange<'_>) -> Result<(), RegistryError> {
+        let Some(path) = self.persist_path.as_ref() else {
+            return Ok(());
+        };
+
+        let feed_id = match &change {
+            FeedChange::Added(config) => config.id(),
+            FeedChange::Removed(id) => id.to_string(),
+        };
+        config::save_feed_change(path, change)
+            .map_err(|e| RegistryError::Persist(e.to_string()))?;
+        info!(path = %path.display(), feed_id = %feed_id, "Feed change written to configuration");
+        Ok(())
+    }
+
+    /// Spawn a scheduler for a built (enabled) feed; disabled feeds are only tracked
+    fn spawn(&self, config: &FeedConfig, feed: Option<Box<dyn PriceFeed>>) -> RunningFeed {
+        let task = feed.map(|feed| {
+            let source = feed.name().to_string();
+            let (commands, receiver) = mpsc::channel(COMMAND_BUFFER);
+            let scheduler = FeedScheduler::new(
+                config.id(),
+                feed,
+                config.interval_ms,
+                self.state.clone(),
+                self.metrics.clone(),
+                receiver,
+            );
+
+            info!(
+                feed_id = %config.id(),
+                feed_type = %config.feed_type,
+                pair = %config.pair(),
+                interval_ms = %config.interval_ms,
+                "Feed scheduler started"
+            );
+
+            let handle = tokio::spawn(async move {
+                scheduler.run().await;
+            });
+
+            FeedTask {
+                handle,
+                source,
+                commands,
+                paused: false,
+            }
+        });
+
+        RunningFeed {
+            config: config.clone(),
+            task,
+        }
+    }
+
+    async fn stop(&self, entry: RunningFeed) {
+        let id = entry.config.id();
+
+        let source = entry.task.map(|task| {
+            task.handle.abort();
+            info!(feed_id = %id, "Feed scheduler stopped");
+            task.source
+        });
+
+        self.state
+            .unregister_feed(&id, &entry.config.pair(), source.as_deref())
+            .await;
+    }
+}
+
+fn find_task<'a>(
+    running: &'a mut [RunningFeed],
+    id: &str,
+) -> Result<&'a mut FeedTask, RegistryError> {
+    let entry = running
+        .iter_mut()
+        .find(|entry| entry.config.id() == id)
+        .ok_or_else(|| RegistryError::FeedNotFound(id.to_string()))?;
+
+    entry
+        .task
+        .as_mut()
+        .ok_or_else(|| RegistryError::FeedDisabled(id.to_string()))
+}
+
+/// A constructed feed (`None` when disabled or skipped) and the error that skipped it
+type BuiltFeed = (Option<Box<dyn PriceFeed>>, Option<String>);
+
+/// Build a feed, tolerating failures of feeds marked `optional`: those are
+/// tracked without a scheduler, along with the error to report
+fn build_or_skip(config: &FeedConfig, http_client: &Client) -> Result<BuiltFeed, RegistryError> {
+    match build_feed(config, http_client) {
+        Ok(feed) => Ok((feed, None)),
+        Err(e) if config.optional => {
+            warn!(
+                feed_id = %config.id(),
+                feed_type = %config.feed_type,
+                error = %e,
+                "Failed to create optional feed, skipping"
+            );
+            Ok((None, Some(e.to_string())))
+        }
+        Err(e) => Err(RegistryError::InvalidFeed(format!(
+            "{}: {}",
+            config.id(),
+            e
+        ))),
+    }
+}
+
+/// Only enabled feeds are constructed
+fn build_feed(
+    config: &FeedConfig,
+    http_client: &Client,
+) -> Result<Option<Box<dyn PriceFeed>>, FeedError> {
+    if !config.enabled {
+        return Ok(None);
+    }
+
+    create_feed(config, http_client.clone()).map(Some)
+}
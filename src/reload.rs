@@ -0,0 +1,154 @@
+use notify::{RecursiveMode, Watcher};
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio::signal::unix::{signal, SignalKind};
+use tokio::sync::mpsc;
+use tracing::{error, info, warn};
+
+use crate::config::Config;
+use crate::registry::FeedRegistry;
+use crate::state::AppState;
+
+/// Editors often emit several events per save; wait for them to settle
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Re-applies the config file on change or SIGHUP. Only `feeds`, `pairs` and
+/// `server.staleness_threshold_secs` are applied live; other sections need a restart.
+pub struct ConfigReloader {
+    path: PathBuf,
+    current: Config,
+    registry: FeedRegistry,
+    state: AppState,
+}
+
+impl ConfigReloader {
+    pub fn new(path: PathBuf, current: Config, registry: FeedRegistry, state: AppState) -> Self {
+        Self {
+            path,
+            current,
+            registry,
+            state,
+        }
+    }
+
+    pub async fn run(mut self) -> anyhow::Result<()> {
+        let (tx, mut file_events) = mpsc::unbounded_channel();
+        let watched = self.path.clone();
+        let file_name = watched.file_name().map(|name| name.to_os_string());
+
+        // Watch the directory rather than the file so atomic replace-on-save is seen
+        let mut watcher =
+            notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+                let Ok(event) = event else {
+                    return;
+                };
+
+                // Reading the file during a reload emits access events; ignore those
+                let content_changed =
+                    event.kind.is_modify() || event.kind.is_create() || event.kind.is_remove();
+                let is_config = event
+                    .paths
+                    .iter()
+                    .any(|path| path.file_name().map(|name| name.to_os_string()) == file_name);
+
+                if content_changed && is_config {
+                    let _ = tx.send(());
+                }
+            })?;
+        let directory = match watched.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+        watcher.watch(&directory, RecursiveMode::NonRecursive)?;
+
+        let mut hangup = signal(SignalKind::hangup())?;
+
+        info!(path = %self.path.display(), "Watching configuration for changes");
+
+        loop {
+            tokio::select! {
+                Some(()) = file_events.recv() => {
+                    tokio::time::sleep(DEBOUNCE).await;
+                    while file_events.try_recv().is_ok() {}
+                    self.reload("file_change").await;
+                }
+                _ = hangup.recv() => self.reload("sighup").await,
+            }
+        }
+    }
+
+    async fn reload(&mut self, trigger: &str) {
+        let config = match Config::load(&self.path) {
+            Ok(config) => config,
+            Err(e) => {
+                error!(
+                    trigger,
+                    path = %self.path.display(),
+                    error = %e,
+                    "Rejected configuration reload, keeping current configuration"
+                );
+                return;
+            }
+        };
+
+        let summary = match self.registry.apply(&config.feeds).await {
+            Ok(summary) => summary,
+            Err(e) => {
+                error!(
+                    trigger,
+                    error = %e,
+                    "Rejected configuration reload, keeping current configuration"
+                );
+                return;
+            }
+        };
+
+        let old_threshold = self.current.server.staleness_threshold_secs;
+        let new_threshold = config.server.staleness_threshold_secs;
+        if old_threshold != new_threshold {
+            self.state.set_staleness_threshold_secs(new_threshold);
+        }
+
+        let pairs_changed = self.current.pairs != config.pairs;
+        if pairs_changed {
+            self.state.set_pair_rules(&config.pairs).await;
+        }
+
+        if summary.is_empty() && old_threshold == new_threshold && !pairs_changed {
+            info!(trigger, "Configuration reloaded, no changes");
+        } else {
+            info!(
+                trigger,
+                added = ?summary.added,
+                removed = ?summary.removed,
+                updated = ?summary.updated,
+                unchanged = summary.unchanged,
+                staleness_threshold_secs = new_threshold,
+                pairs_changed,
+                "Configuration reloaded"
+            );
+        }
+
+        if restart_required(&self.current, &config) {
+            warn!(
+                trigger,
+                "Changes outside feeds, pairs and staleness threshold require a restart"
+            );
+        }
+
+        self.current = config;
+    }
+}
+
+/// Compares the sections that are only read at startup
+fn restart_required(old: &Config, new: &Config) -> bool {
+    old.server.port != new.server.port
+        || old.server.http != new.server.http
+        || old.tokens != new.tokens
+        || old.health != new.health
+        || old.sinks != new.sinks
+        || old.alerts != new.alerts
+        || old.admin != new.admin
+        || old.signing != new.signing
+        || old.api_keys != new.api_keys
+}
@@ -1,74 +1,139 @@
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
+use tokio::sync::{mpsc, oneshot};
 use tokio::time::interval;
 use tracing::{error, info};
 
+use crate::error::FeedError;
 use crate::feeds::PriceFeed;
 use crate::metrics::Metrics;
+use crate::models::PriceData;
 use crate::state::AppState;
 
+/// Control messages sent to a running scheduler by the `FeedRegistry`
+pub enum SchedulerCommand {
+    /// Skip scheduled ticks until resumed
+    Pause,
+    Resume,
+    /// Fetch immediately, even while paused, and reply with the result
+    FetchNow(oneshot::Sender<Result<PriceData, FeedError>>),
+}
+
 pub struct FeedScheduler {
+    feed_id: String,
     feed: Box<dyn PriceFeed>,
     interval_ms: u64,
     state: AppState,
     metrics: Arc<Metrics>,
+    commands: mpsc::Receiver<SchedulerCommand>,
 }
 
 impl FeedScheduler {
     pub fn new(
+        feed_id: String,
         feed: Box<dyn PriceFeed>,
         interval_ms: u64,
         state: AppState,
         metrics: Arc<Metrics>,
+        commands: mpsc::Receiver<SchedulerCommand>,
     ) -> Self {
         Self {
+            feed_id,
             feed,
             interval_ms,
             state,
             metrics,
+            commands,
         }
     }
 
-    pub async fn run(self) {
-        let feed_name = self.feed.name().to_string();
-        let pair = self.feed.pair().to_string();
-        let priority = self.feed.priority();
-
+    pub async fn run(mut self) {
         info!(
-            feed = %feed_name,
-            pair = %pair,
+            feed = %self.feed.name(),
+            pair = %self.feed.pair(),
             interval_ms = %self.interval_ms,
             "Starting price feed scheduler"
         );
 
         let mut ticker = interval(Duration::from_millis(self.interval_ms));
+        let mut paused = false;
 
         loop {
-            ticker.tick().await;
+            tokio::select! {
+                _ = ticker.tick() => {
+                    if !paused {
+                        let _ = self.fetch().await;
+                    }
+                }
+                command = self.commands.recv() => match command {
+                    Some(SchedulerCommand::Pause) => paused = true,
+                    Some(SchedulerCommand::Resume) => paused = false,
+                    Some(SchedulerCommand::FetchNow(reply)) => {
+                        let _ = reply.send(self.fetch().await);
+                    }
+                    // The registry dropped its handle
+                    None => break,
+                },
+            }
+        }
+    }
+
+    async fn fetch(&self) -> Result<PriceData, FeedError> {
+        let feed_name = self.feed.name();
+        let pair = self.feed.pair();
+        let priority = self.feed.priority();
 
-            match self.feed.fetch_price().await {
-                Ok(price_data) => {
-                    info!(
-                        feed = %feed_name,
-                        pair = %pair,
-                        price = %price_data.price,
-                        "Fetched price"
-                    );
+        let start = Instant::now();
+        let result = self.feed.fetch_price().await;
+        self.metrics
+            .record_fetch_duration(feed_name, pair, start.elapsed().as_secs_f64());
 
-                    self.metrics
-                        .record_fetch_success(&feed_name, &pair, &price_data.price);
-                    self.state.update_price(price_data, priority).await;
-                }
-                Err(e) => {
-                    error!(
-                        feed = %feed_name,
-                        pair = %pair,
-                        error = %e,
-                        "Failed to fetch price"
-                    );
+        // Prices breaking the pair's sanity bounds count as failed fetches
+        let result = match result {
+            Ok(price_data) => self
+                .state
+                .update_price(price_data.clone(), priority)
+                .await
+                .map(|()| price_data),
+            Err(e) => Err(e),
+        };
 
-                    self.metrics.record_fetch_error(&feed_name, &pair, &e);
+        match result {
+            Ok(price_data) => {
+                info!(
+                    feed = %feed_name,
+                    pair = %pair,
+                    price = %price_data.price,
+                    "Fetched price"
+                );
+
+                self.metrics
+                    .record_fetch_success(feed_name, pair, &price_data.price);
+                let tick_age = chrono::Utc::now() - price_data.timestamp;
+                self.metrics.record_tick_age(
+                    feed_name,
+                    pair,
+                    tick_age.num_milliseconds() as f64 / 1000.0,
+                );
+                if let Some(impact) = &price_data.price_impact_pct {
+                    self.metrics.record_price_impact(feed_name, pair, impact);
                 }
+                self.state.record_feed_success(&self.feed_id).await;
+                Ok(price_data)
+            }
+            Err(e) => {
+                error!(
+                    feed = %feed_name,
+                    pair = %pair,
+                    error = %e,
+                    "Failed to fetch price"
+                );
+
+                self.metrics.record_fetch_error(feed_name, pair, &e);
+                self.state
+                    .record_feed_error(&self.feed_id, &e.to_string())
+                    .await;
+                Err(e)
             }
         }
     }
@@ -0,0 +1,231 @@
+use chrono::{DateTime, Utc};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use std::path::Path;
+
+use crate::error::SigningError;
+
+/// Domain separator so signatures cannot be replayed as other message types
+const CANONICAL_PREFIX: &str = "rate-relay/price/v1";
+
+/// Signs price attestations with an ed25519 key loaded from disk
+pub struct PriceSigner {
+    signing_key: SigningKey,
+}
+
+impl PriceSigner {
+    /// Key file holds the 32-byte secret seed, either raw or hex-encoded
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, SigningError> {
+        let content = std::fs::read(path)?;
+
+        let seed: [u8; 32] = match std::str::from_utf8(&content).map(str::trim) {
+            Ok(text) if text.len() == 64 => hex::decode(text)
+                .map_err(|e| SigningError::InvalidKey(e.to_string()))?
+                .try_into()
+                .expect("64 hex chars decode to 32 bytes"),
+            _ => content.as_slice().try_into().map_err(|_| {
+                SigningError::InvalidKey(format!(
+                    "expected 32 raw bytes or 64 hex chars, got {} bytes",
+                    content.len()
+                ))
+            })?,
+        };
+
+        let signer = Self {
+            signing_key: SigningKey::from_bytes(&seed),
+        };
+
+        // Round-trip once so a broken key fails at startup rather than per request
+        let now = Utc::now();
+        let signature = signer.sign("SELF/TEST", "1", "self-test", &now);
+        verify(
+            &signer.public_key_hex(),
+            "SELF/TEST",
+            "1",
+            "self-test",
+            &now,
+            &signature,
+        )?;
+
+        Ok(signer)
+    }
+
+    pub fn public_key_hex(&self) -> String {
+        hex::encode(self.signing_key.verifying_key().as_bytes())
+    }
+
+    /// Hex-encoded signature over the canonical encoding of the attestation
+    pub fn sign(&self, pair: &str, price: &str, source: &str, timestamp: &DateTime<Utc>) -> String {
+        let message = canonical_message(pair, price, source, timestamp);
+        hex::encode(self.signing_key.sign(&message).to_bytes())
+    }
+}
+
+/// `rate-relay/price/v1\n<pair>\n<price>\n<source>\n<unix millis>`, where `price`
+/// is exactly the string served in `PriceResponse.price`
+pub fn canonical_message(
+    pair: &str,
+    price: &str,
+    source: &str,
+    timestamp: &DateTime<Utc>,
+) -> Vec<u8> {
+    format!(
+        "{}\n{}\n{}\n{}\n{}",
+        CANONICAL_PREFIX,
+        pair,
+        price,
+        source,
+        timestamp.timestamp_millis()
+    )
+    .into_bytes()
+}
+
+/// Check a hex signature produced by `PriceSigner::sign` against a hex public key
+pub fn verify(
+    public_key_hex: &str,
+    pair: &str,
+    price: &str,
+    source: &str,
+    timestamp: &DateTime<Utc>,
+    signature_hex: &str,
+) -> Result<(), SigningError> {
+    let public_key: [u8; 32] = hex::decode(public_key_hex)
+        .map_err(|e| SigningError::InvalidKey(e.to_string()))?
+        .try_into()
+        .map_err(|_| SigningError::InvalidKey("public key must be 32 bytes".to_string()))?;
+    let verifying_key = VerifyingKey::from_bytes(&public_key)
+        .map_err(|e| SigningError::InvalidKey(e.to_string()))?;
+
+    let signature: [u8; 64] = hex::decode(signature_hex)
+        .map_err(|_| SigningError::InvalidSignature)?
+        .try_into()
+        .map_err(|_| SigningError::InvalidSignature)?;
+
+    verifying_key
+        .verify(
+            &canonical_message(pair, price, source, timestamp),
+            &Signature::from_bytes(&signature),
+        )
+        .map_err(|_| SigningError::InvalidSignature)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn signer(seed: u8) -> PriceSigner {
+        PriceSigner {
+            signing_key: SigningKey::from_bytes(&[seed; 32]),
+        }
+    }
+
+    fn timestamp() -> DateTime<Utc> {
+        Utc.timestamp_millis_opt(1_700_000_000_123).unwrap()
+    }
+
+    #[test]
+    fn canonical_message_layout() {
+        assert_eq!(
+            canonical_message("SOL/USDC", "150.25", "jupiter", &timestamp()),
+            b"rate-relay/price/v1\nSOL/USDC\n150.25\njupiter\n1700000000123"
+        );
+    }
+
+    #[test]
+    fn signature_verifies_with_the_signer_key() {
+        let signer = signer(7);
+        let signature = signer.sign("SOL/USDC", "150.25", "jupiter", &timestamp());
+
+        assert!(verify(
+            &signer.public_key_hex(),
+            "SOL/USDC",
+            "150.25",
+            "jupiter",
+            &timestamp(),
+            &signature
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn tampered_attestation_is_rejected() {
+        let signer = signer(7);
+        let key = signer.public_key_hex();
+        let signature = signer.sign("SOL/USDC", "150.25", "jupiter", &timestamp());
+        let later = timestamp() + chrono::Duration::milliseconds(1);
+
+        for (pair, price, source, timestamp) in [
+            ("SOL/USDT", "150.25", "jupiter", timestamp()),
+            ("SOL/USDC", "150.250", "jupiter", timestamp()),
+            ("SOL/USDC", "150.25", "mock", timestamp()),
+            ("SOL/USDC", "150.25", "jupiter", later),
+        ] {
+            assert!(matches!(
+                verify(&key, pair, price, source, &timestamp, &signature),
+                Err(SigningError::InvalidSignature)
+            ));
+        }
+
+        let mut flipped = hex::decode(&signature).unwrap();
+        flipped[0] ^= 1;
+        assert!(matches!(
+            verify(
+                &key,
+                "SOL/USDC",
+                "150.25",
+                "jupiter",
+                &timestamp(),
+                &hex::encode(flipped)
+            ),
+            Err(SigningError::InvalidSignature)
+        ));
+    }
+
+    #[test]
+    fn signature_from_another_key_is_rejected() {
+        let signature = signer(7).sign("SOL/USDC", "150.25", "jupiter", &timestamp());
+
+        assert!(matches!(
+            verify(
+                &signer(8).public_key_hex(),
+                "SOL/USDC",
+                "150.25",
+                "jupiter",
+                &timestamp(),
+                &signature
+            ),
+            Err(SigningError::InvalidSignature)
+        ));
+    }
+
+    #[test]
+    fn key_file_accepts_raw_and_hex_seeds() {
+        let directory =
+            std::env::temp_dir().join(format!("rate-relay-signing-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let expected = signer(7).public_key_hex();
+
+        let raw = directory.join("raw.key");
+        std::fs::write(&raw, [7u8; 32]).unwrap();
+        assert_eq!(
+            PriceSigner::from_file(&raw).unwrap().public_key_hex(),
+            expected
+        );
+
+        let hex_file = directory.join("hex.key");
+        std::fs::write(&hex_file, format!("{}\n", hex::encode([7u8; 32]))).unwrap();
+        assert_eq!(
+            PriceSigner::from_file(&hex_file).unwrap().public_key_hex(),
+            expected
+        );
+
+        let short = directory.join("short.key");
+        std::fs::write(&short, [7u8; 16]).unwrap();
+        assert!(matches!(
+            PriceSigner::from_file(&short),
+            Err(SigningError::InvalidKey(_))
+        ));
+
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+}
@@ -0,0 +1,225 @@
+use async_nats::jetstream;
+use std::sync::Arc;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tracing::{error, info, warn};
+
+use crate::config::NatsSinkConfig;
+use crate::error::SinkError;
+use crate::metrics::Metrics;
+use crate::models::{BestPriceChange, PriceResponse};
+
+/// Publishes every best-price change as JSON to `<prefix>.<BASE>.<QUOTE>`
+pub struct NatsSink {
+    client: async_nats::Client,
+    jetstream: Option<jetstream::Context>,
+    subject_prefix: String,
+    metrics: Arc<Metrics>,
+}
+
+impl NatsSink {
+    pub async fn connect(
+        config: &NatsSinkConfig,
+        metrics: Arc<Metrics>,
+    ) -> Result<Self, SinkError> {
+        let client = async_nats::connect(&config.url)
+            .await
+            .map_err(|e| SinkError::Connect(e.to_string()))?;
+
+        let jetstream = if config.jetstream {
+            let context = jetstream::new(client.clone());
+
+            // Capture every pair published under the prefix in a single stream
+            context
+                .get_or_create_stream(jetstream::stream::Config {
+                    name: config.stream.clone(),
+                    subjects: vec![format!("{}.>", config.subject_prefix)],
+                    ..Default::default()
+                })
+                .await
+                .map_err(|e| SinkError::Connect(e.to_string()))?;
+
+            Some(context)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            client,
+            jetstream,
+            subject_prefix: config.subject_prefix.clone(),
+            metrics,
+        })
+    }
+
+    pub async fn run(self, mut changes: broadcast::Receiver<BestPriceChange>) {
+        info!(
+            subject_prefix = %self.subject_prefix,
+            jetstream = self.jetstream.is_some(),
+            "Starting NATS sink"
+        );
+
+        loop {
+            let change = match changes.recv().await {
+                Ok(change) => change,
+                Err(RecvError::Lagged(skipped)) => {
+                    warn!(skipped, "NATS sink lagging behind, dropped price changes");
+                    continue;
+                }
+                Err(RecvError::Closed) => break,
+            };
+
+            let subject = self.subject(&change.data.pair);
+
+            match self.publish(&subject, &change).await {
+                Ok(()) => self.metrics.record_sink_publish("nats", true),
+                Err(e) => {
+                    error!(subject = %subject, error = %e, "Failed to publish price change");
+                    self.metrics.record_sink_publish("nats", false);
+                }
+            }
+        }
+    }
+
+    async fn publish(&self, subject: &str, change: &BestPriceChange) -> Result<(), SinkError> {
+        let response = PriceResponse::from_price_data(&change.data, change.fallback_used);
+        let payload = serde_json::to_vec(&response)?;
+
+        match &self.jetstream {
+            Some(context) => {
+                context
+                    .publish(subject.to_string(), payload.into())
+                    .await
+                    .map_err(|e| SinkError::Publish(e.to_string()))?
+                    .await
+                    .map_err(|e| SinkError::Publish(e.to_string()))?;
+            }
+            None => {
+                self.client
+                    .publish(subject.to_string(), payload.into())
+                    .await
+                    .map_err(|e| SinkError::Publish(e.to_string()))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// "SOL/USDC" -> "prices.SOL.USDC"
+    fn subject(&self, pair: &str) -> String {
+        format!("{}.{}", self.subject_prefix, pair.replace('/', "."))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use futures_util::StreamExt;
+    use rust_decimal::Decimal;
+    use std::time::Duration;
+
+    use crate::models::PriceData;
+
+    const TIMEOUT: Duration = Duration::from_secs(5);
+
+    /// Server for the ignored tests, e.g. a local `nats-server -js`
+    fn nats_url() -> String {
+        std::env::var("NATS_TEST_URL").unwrap_or_else(|_| "nats://127.0.0.1:4222".to_string())
+    }
+
+    fn sink_config(subject_prefix: &str, jetstream: bool, stream: &str) -> NatsSinkConfig {
+        NatsSinkConfig {
+            url: nats_url(),
+            subject_prefix: subject_prefix.to_string(),
+            jetstream,
+            stream: stream.to_string(),
+            enabled: true,
+        }
+    }
+
+    fn change() -> BestPriceChange {
+        BestPriceChange {
+            data: PriceData {
+                pair: "SOL/USDC".to_string(),
+                price: Decimal::new(14250, 2),
+                source: "jupiter".to_string(),
+                timestamp: Utc::now(),
+                slot: Some(312345678),
+                price_impact_pct: None,
+            },
+            fallback_used: true,
+            previous_source: Some("pyth".to_string()),
+        }
+    }
+
+    fn assert_payload(payload: &[u8]) {
+        let body: serde_json::Value = serde_json::from_slice(payload).unwrap();
+        assert_eq!(body["pair"], "SOL/USDC");
+        assert_eq!(body["price"], "142.50");
+        assert_eq!(body["source"], "jupiter");
+        assert_eq!(body["fallback_used"], true);
+        assert_eq!(body["slot"], 312345678);
+        assert!(body.get("price_impact_pct").is_none());
+    }
+
+    /// Spawn a connected sink; dropping the sender stops it
+    async fn start_sink(
+        config: &NatsSinkConfig,
+    ) -> (
+        broadcast::Sender<BestPriceChange>,
+        tokio::task::JoinHandle<()>,
+    ) {
+        let sink = NatsSink::connect(config, Arc::new(Metrics::new()))
+            .await
+            .unwrap();
+        let (changes, receiver) = broadcast::channel(4);
+        (changes, tokio::spawn(sink.run(receiver)))
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a NATS server; set NATS_TEST_URL"]
+    async fn publishes_best_price_change_as_json() {
+        let client = async_nats::connect(nats_url()).await.unwrap();
+        let mut subscriber = client.subscribe("prices.SOL.USDC").await.unwrap();
+        client.flush().await.unwrap();
+
+        // Core publishes are buffered, so keep the sink running until delivery
+        let (changes, sink) = start_sink(&sink_config("prices", false, "PRICES")).await;
+        changes.send(change()).unwrap();
+
+        let message = tokio::time::timeout(TIMEOUT, subscriber.next())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_payload(&message.payload);
+
+        drop(changes);
+        tokio::time::timeout(TIMEOUT, sink).await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a NATS server with JetStream enabled; set NATS_TEST_URL"]
+    async fn jetstream_persists_best_price_change() {
+        // Unique names so runs against a shared server don't see each other's messages
+        let suffix = format!("{}_{}", std::process::id(), Utc::now().timestamp_millis());
+        let prefix = format!("rate_relay_test_{}", suffix);
+        let stream_name = format!("RATE_RELAY_TEST_{}", suffix);
+
+        // JetStream publishes wait for the ack, so the message is stored once the sink stops
+        let (changes, sink) = start_sink(&sink_config(&prefix, true, &stream_name)).await;
+        changes.send(change()).unwrap();
+        drop(changes);
+        tokio::time::timeout(TIMEOUT, sink).await.unwrap().unwrap();
+
+        let client = async_nats::connect(nats_url()).await.unwrap();
+        let context = jetstream::new(client);
+        let stream = context.get_stream(&stream_name).await.unwrap();
+        let message = stream
+            .get_last_raw_message_by_subject(&format!("{}.SOL.USDC", prefix))
+            .await
+            .unwrap();
+        assert_payload(&message.payload);
+
+        context.delete_stream(&stream_name).await.unwrap();
+    }
+}
@@ -1,13 +1,26 @@
 use chrono::{DateTime, Duration, Utc};
-use std::collections::HashMap;
+use rust_decimal::Decimal;
+use std::collections::{BTreeSet, HashMap};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use tokio::sync::{broadcast, RwLock};
 
-use crate::models::PriceData;
+use crate::config::{FeedConfig, PairConfig};
+use crate::error::{FeedError, PriceError};
+use crate::models::{
+    AggregationMode, BestPriceChange, ConversionLeg, FeedStatus, PairHealth, PairInfo,
+    PairSourceInfo, PairStatus, PriceData, SourcePrice,
+};
 
 /// Key for identifying a price entry: (pair, source)
 type PriceKey = (String, String);
 
+/// Capacity of the best-price change channel before slow subscribers start lagging
+const CHANGE_CHANNEL_CAPACITY: usize = 1024;
+
+/// Source reported for prices aggregated with `AggregationMode::Median`
+const MEDIAN_SOURCE: &str = "median";
+
 #[derive(Debug, Clone)]
 struct PriceEntry {
     data: PriceData,
@@ -17,74 +30,806 @@ struct PriceEntry {
 #[derive(Clone)]
 pub struct AppState {
     inner: Arc<RwLock<StateInner>>,
-    staleness_threshold_secs: i64,
+    /// Shared across clones so a config reload applies everywhere
+    staleness_threshold_secs: Arc<AtomicU64>,
+    changes: broadcast::Sender<BestPriceChange>,
 }
 
 struct StateInner {
     prices: HashMap<PriceKey, PriceEntry>,
+    /// Every configured feed, in configuration order
+    feeds: Vec<FeedStatus>,
+    /// `[[pairs]]` rules keyed by uppercase pair
+    pair_rules: HashMap<String, PairConfig>,
+}
+
+/// The price served for a pair and whether it came from a fallback source
+struct Selection {
+    data: PriceData,
+    fallback_used: bool,
+}
+
+/// Serving rules for one pair: its `[[pairs]]` entry over the global defaults
+struct PairPolicy<'a> {
+    rules: Option<&'a PairConfig>,
+    staleness_threshold: Duration,
+    min_sources: usize,
+    aggregation: AggregationMode,
 }
 
 impl AppState {
     pub fn new(staleness_threshold_secs: u64) -> Self {
+        let (changes, _) = broadcast::channel(CHANGE_CHANNEL_CAPACITY);
+
         Self {
             inner: Arc::new(RwLock::new(StateInner {
                 prices: HashMap::new(),
+                feeds: Vec::new(),
+                pair_rules: HashMap::new(),
             })),
-            staleness_threshold_secs: staleness_threshold_secs as i64,
+            staleness_threshold_secs: Arc::new(AtomicU64::new(staleness_threshold_secs)),
+            changes,
         }
     }
 
-    /// Store a price update from a feed
-    pub async fn update_price(&self, data: PriceData, priority: u32) {
+    pub fn staleness_threshold_secs(&self) -> u64 {
+        self.staleness_threshold_secs.load(Ordering::Relaxed)
+    }
+
+    pub fn set_staleness_threshold_secs(&self, secs: u64) {
+        self.staleness_threshold_secs.store(secs, Ordering::Relaxed);
+    }
+
+    /// Replace the `[[pairs]]` rules; takes effect on the next lookup
+    pub async fn set_pair_rules(&self, pairs: &[PairConfig]) {
+        let mut state = self.inner.write().await;
+
+        state.pair_rules = pairs
+            .iter()
+            .map(|rules| (rules.pair.to_uppercase(), rules.clone()))
+            .collect();
+    }
+
+    /// Subscribe to changes of the selected (best) price for any pair
+    pub fn subscribe(&self) -> broadcast::Receiver<BestPriceChange> {
+        self.changes.subscribe()
+    }
+
+    /// Store a price update from a feed, unless it breaks the pair's sanity bounds
+    pub async fn update_price(&self, data: PriceData, priority: u32) -> Result<(), FeedError> {
+        let pair = data.pair.clone();
         let key = (data.pair.clone(), data.source.clone());
-        let entry = PriceEntry { data, priority };
 
         let mut state = self.inner.write().await;
-        state.prices.insert(key, entry);
+        let now = Utc::now();
+        let policy = state.policy(&pair, self.staleness_threshold_secs());
+
+        let previous = state
+            .prices
+            .get(&key)
+            .filter(|entry| is_fresh(&entry.data.timestamp, &now, &policy.staleness_threshold));
+        check_bounds(&data, previous.map(|entry| &entry.data.price), policy.rules)?;
+
+        let previous = resolve(&state.prices, &pair, &policy, &now)
+            .ok()
+            .map(|selection| (selection.data.source, selection.data.price));
+
+        state.prices.insert(key, PriceEntry { data, priority });
+
+        let policy = state.policy(&pair, self.staleness_threshold_secs());
+        let Ok(best) = resolve(&state.prices, &pair, &policy, &now) else {
+            return Ok(());
+        };
+
+        let changed = match &previous {
+            Some((source, price)) => *source != best.data.source || *price != best.data.price,
+            None => true,
+        };
+
+        if changed {
+            // Sending only fails when nobody is subscribed, which is fine
+            let _ = self.changes.send(BestPriceChange {
+                data: best.data,
+                fallback_used: best.fallback_used,
+                previous_source: previous.map(|(source, _)| source),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// The price served for a pair under its `[[pairs]]` rules
+    pub async fn get_price(&self, pair: &str) -> Result<(PriceData, bool), PriceError> {
+        let state = self.inner.read().await;
+        let now = Utc::now();
+        let policy = state.policy(pair, self.staleness_threshold_secs());
+
+        resolve(&state.prices, pair, &policy, &now)
+            .map(|selection| (selection.data, selection.fallback_used))
     }
 
-    /// Get the best available price for a pair (lowest priority number that has fresh data)
-    pub async fn get_price(&self, pair: &str) -> Option<(PriceData, bool)> {
+    /// Best prices for many pairs from a single consistent snapshot, in input order
+    pub async fn get_prices(&self, pairs: &[String]) -> Vec<Result<(PriceData, bool), PriceError>> {
         let state = self.inner.read().await;
         let now = Utc::now();
-        let staleness_threshold = Duration::seconds(self.staleness_threshold_secs);
+        let global_threshold_secs = self.staleness_threshold_secs();
+
+        pairs
+            .iter()
+            .map(|pair| {
+                let policy = state.policy(pair, global_threshold_secs);
+                resolve(&state.prices, pair, &policy, &now)
+                    .map(|selection| (selection.data, selection.fallback_used))
+            })
+            .collect()
+    }
+
+    /// Legs to convert `from` into `to`: the direct pair, its inverse, or a
+    /// two-hop path through one intermediate token. Empty when `from == to`.
+    pub async fn get_conversion_path(&self, from: &str, to: &str) -> Option<Vec<ConversionLeg>> {
+        if from == to {
+            return Some(Vec::new());
+        }
+
+        let state = self.inner.read().await;
+        let now = Utc::now();
+        let global_threshold_secs = self.staleness_threshold_secs();
+
+        let pairs: BTreeSet<&str> = state.prices.keys().map(|(pair, _)| pair.as_str()).collect();
+        let select = |pair: String| -> Option<PriceData> {
+            let policy = state.policy(&pair, global_threshold_secs);
+            resolve(&state.prices, &pair, &policy, &now)
+                .ok()
+                .map(|selection| selection.data)
+        };
+        let leg = |a: &str, b: &str| -> Option<ConversionLeg> {
+            if let Some(data) = select(format!("{}/{}", a, b)) {
+                return Some(ConversionLeg {
+                    data,
+                    inverted: false,
+                });
+            }
+
+            select(format!("{}/{}", b, a)).map(|data| ConversionLeg {
+                data,
+                inverted: true,
+            })
+        };
 
-        // Collect all prices for this pair
-        let mut candidates: Vec<&PriceEntry> = state
+        if let Some(direct) = leg(from, to) {
+            return Some(vec![direct]);
+        }
+
+        let tokens: BTreeSet<&str> = pairs
+            .iter()
+            .filter_map(|pair| pair.split_once('/'))
+            .flat_map(|(base, quote)| [base, quote])
+            .filter(|token| *token != from && *token != to)
+            .collect();
+
+        for via in tokens {
+            if let (Some(first), Some(second)) = (leg(from, via), leg(via, to)) {
+                return Some(vec![first, second]);
+            }
+        }
+
+        None
+    }
+
+    /// Get the price from one specific source, if it is fresh
+    pub async fn get_price_from_source(
+        &self,
+        pair: &str,
+        source: &str,
+    ) -> Option<(PriceData, bool)> {
+        let state = self.inner.read().await;
+        let now = Utc::now();
+        let policy = state.policy(pair, self.staleness_threshold_secs());
+
+        let entry = state
+            .prices
+            .get(&(pair.to_string(), source.to_string()))
+            .filter(|entry| is_fresh(&entry.data.timestamp, &now, &policy.staleness_threshold))?;
+
+        Some((entry.data.clone(), entry.priority > 1))
+    }
+
+    /// Latest price from every source for a pair, stale ones included, ordered by priority.
+    /// With median aggregation every fresh source counts as selected.
+    pub async fn get_sources(&self, pair: &str) -> Vec<SourcePrice> {
+        let state = self.inner.read().await;
+        let now = Utc::now();
+        let policy = state.policy(pair, self.staleness_threshold_secs());
+
+        let selected = resolve(&state.prices, pair, &policy, &now)
+            .ok()
+            .map(|selection| selection.data.source);
+
+        let mut sources: Vec<SourcePrice> = state
             .prices
             .iter()
             .filter(|((p, _), _)| p == pair)
-            .map(|(_, entry)| entry)
-            .filter(|entry| is_fresh(&entry.data.timestamp, &now, &staleness_threshold))
+            .map(|((_, source), entry)| {
+                let fresh = is_fresh(&entry.data.timestamp, &now, &policy.staleness_threshold);
+                let selected = match selected.as_deref() {
+                    Some(MEDIAN_SOURCE) => fresh,
+                    selected => selected == Some(source.as_str()),
+                };
+
+                SourcePrice {
+                    data: entry.data.clone(),
+                    priority: entry.priority,
+                    fresh,
+                    selected,
+                }
+            })
             .collect();
 
-        if candidates.is_empty() {
-            return None;
+        sources.sort_by(|a, b| {
+            a.priority
+                .cmp(&b.priority)
+                .then_with(|| a.data.source.cmp(&b.data.source))
+        });
+
+        sources
+    }
+
+    /// Track a configured feed so it shows up in discovery endpoints
+    pub async fn register_feed(&self, config: &FeedConfig) {
+        let mut state = self.inner.write().await;
+        let id = config.id();
+
+        state.feeds.retain(|feed| feed.id != id);
+        state.feeds.push(FeedStatus {
+            id,
+            feed_type: config.feed_type.clone(),
+            pair: config.pair(),
+            interval_ms: config.interval_ms,
+            priority: config.priority,
+            enabled: config.enabled,
+            paused: false,
+            last_success_at: None,
+            last_error_at: None,
+            consecutive_errors: 0,
+            last_error: None,
+        });
+    }
+
+    /// Forget a feed and any prices it reported under `source`
+    pub async fn unregister_feed(&self, feed_id: &str, pair: &str, source: Option<&str>) {
+        let mut state = self.inner.write().await;
+
+        state.feeds.retain(|feed| feed.id != feed_id);
+        if let Some(source) = source {
+            state.prices.remove(&(pair.to_string(), source.to_string()));
+        }
+    }
+
+    pub async fn record_feed_success(&self, feed_id: &str) {
+        let mut state = self.inner.write().await;
+
+        if let Some(feed) = state.feeds.iter_mut().find(|feed| feed.id == feed_id) {
+            feed.last_success_at = Some(Utc::now());
+            feed.consecutive_errors = 0;
+        }
+    }
+
+    pub async fn record_feed_error(&self, feed_id: &str, error: &str) {
+        let mut state = self.inner.write().await;
+
+        if let Some(feed) = state.feeds.iter_mut().find(|feed| feed.id == feed_id) {
+            feed.last_error_at = Some(Utc::now());
+            feed.consecutive_errors += 1;
+            feed.last_error = Some(error.to_string());
+        }
+    }
+
+    pub async fn set_feed_paused(&self, feed_id: &str, paused: bool) {
+        let mut state = self.inner.write().await;
+
+        if let Some(feed) = state.feeds.iter_mut().find(|feed| feed.id == feed_id) {
+            feed.paused = paused;
         }
+    }
+
+    pub async fn feeds(&self) -> Vec<FeedStatus> {
+        self.inner.read().await.feeds.clone()
+    }
+
+    pub async fn feed(&self, feed_id: &str) -> Option<FeedStatus> {
+        let state = self.inner.read().await;
+        state.feeds.iter().find(|feed| feed.id == feed_id).cloned()
+    }
+
+    /// Every pair with at least one configured feed, sorted by pair
+    pub async fn pairs(&self) -> Vec<PairInfo> {
+        let state = self.inner.read().await;
+        let global_threshold_secs = self.staleness_threshold_secs();
+        let mut pairs: Vec<PairInfo> = Vec::new();
 
-        // Sort by priority (lower is better)
-        candidates.sort_by_key(|e| e.priority);
+        for feed in &state.feeds {
+            let source = PairSourceInfo {
+                feed_id: feed.id.clone(),
+                source: feed.feed_type.clone(),
+                priority: feed.priority,
+                enabled: feed.enabled,
+            };
 
-        let best = candidates.first()?;
-        let fallback_used = best.priority > 1;
+            match pairs.iter_mut().find(|info| info.pair == feed.pair) {
+                Some(info) => info.sources.push(source),
+                None => {
+                    let policy = state.policy(&feed.pair, global_threshold_secs);
+                    pairs.push(PairInfo {
+                        pair: feed.pair.clone(),
+                        sources: vec![source],
+                        aggregation: policy.aggregation,
+                        staleness_threshold_secs: policy.staleness_threshold.num_seconds() as u64,
+                        min_sources: policy.min_sources,
+                    })
+                }
+            }
+        }
+
+        pairs.sort_by(|a, b| a.pair.cmp(&b.pair));
+        for info in &mut pairs {
+            info.sources.sort_by_key(|source| source.priority);
+        }
 
-        Some((best.data.clone(), fallback_used))
+        pairs
     }
 
-    /// Check if we have any fresh data at all
-    pub async fn has_fresh_data(&self) -> bool {
+    /// Health of every configured pair plus any `required_pairs` without a feed.
+    /// With no explicit `required_pairs`, every pair with an enabled feed is required.
+    pub async fn pair_health(&self, required_pairs: &[String]) -> Vec<PairHealth> {
         let state = self.inner.read().await;
         let now = Utc::now();
-        let staleness_threshold = Duration::seconds(self.staleness_threshold_secs);
+        let global_threshold_secs = self.staleness_threshold_secs();
 
-        state
-            .prices
-            .values()
-            .any(|entry| is_fresh(&entry.data.timestamp, &now, &staleness_threshold))
+        let mut pairs: Vec<&str> = state
+            .feeds
+            .iter()
+            .map(|feed| feed.pair.as_str())
+            .chain(required_pairs.iter().map(String::as_str))
+            .collect();
+        pairs.sort_unstable();
+        pairs.dedup();
+
+        pairs
+            .into_iter()
+            .map(|pair| {
+                let required = if required_pairs.is_empty() {
+                    state
+                        .feeds
+                        .iter()
+                        .any(|feed| feed.enabled && feed.pair == pair)
+                } else {
+                    required_pairs.iter().any(|p| p == pair)
+                };
+                let policy = state.policy(pair, global_threshold_secs);
+
+                let error = match resolve(&state.prices, pair, &policy, &now) {
+                    Ok(selection) => {
+                        let fallback = selection.fallback_used;
+                        return PairHealth {
+                            pair: pair.to_string(),
+                            status: if fallback {
+                                PairStatus::Fallback
+                            } else {
+                                PairStatus::Fresh
+                            },
+                            required,
+                            source: Some(selection.data.source.clone()),
+                            age_ms: Some((now - selection.data.timestamp).num_milliseconds()),
+                            reason: fallback.then(|| {
+                                format!(
+                                    "Primary source unavailable, using {}",
+                                    selection.data.source
+                                )
+                            }),
+                        };
+                    }
+                    Err(error) => error,
+                };
+
+                let entries: Vec<&PriceEntry> = state
+                    .prices
+                    .iter()
+                    .filter(|((p, _), _)| p == pair)
+                    .map(|(_, entry)| entry)
+                    .collect();
+                let latest = entries.iter().max_by_key(|entry| entry.data.timestamp);
+
+                match (error, latest) {
+                    (PriceError::InsufficientSources, Some(entry)) => {
+                        let fresh = entries
+                            .iter()
+                            .filter(|entry| {
+                                is_fresh(&entry.data.timestamp, &now, &policy.staleness_threshold)
+                            })
+                            .count();
+                        PairHealth {
+                            pair: pair.to_string(),
+                            status: PairStatus::InsufficientSources,
+                            required,
+                            source: Some(entry.data.source.clone()),
+                            age_ms: Some((now - entry.data.timestamp).num_milliseconds()),
+                            reason: Some(format!(
+                                "Only {} of {} required sources are fresh",
+                                fresh, policy.min_sources
+                            )),
+                        }
+                    }
+                    (_, Some(entry)) => {
+                        let age = now - entry.data.timestamp;
+                        PairHealth {
+                            pair: pair.to_string(),
+                            status: PairStatus::Stale,
+                            required,
+                            source: Some(entry.data.source.clone()),
+                            age_ms: Some(age.num_milliseconds()),
+                            reason: Some(format!(
+                                "Latest price from {} is {}s old (threshold {}s)",
+                                entry.data.source,
+                                age.num_seconds(),
+                                policy.staleness_threshold.num_seconds()
+                            )),
+                        }
+                    }
+                    (_, None) => PairHealth {
+                        pair: pair.to_string(),
+                        status: PairStatus::Missing,
+                        required,
+                        source: None,
+                        age_ms: None,
+                        reason: Some("No source has reported a price".to_string()),
+                    },
+                }
+            })
+            .collect()
     }
+}
+
+impl StateInner {
+    fn policy(&self, pair: &str, global_threshold_secs: u64) -> PairPolicy<'_> {
+        let rules = self.pair_rules.get(&pair.to_uppercase());
+        let threshold_secs = rules
+            .and_then(|rules| rules.staleness_threshold_secs)
+            .unwrap_or(global_threshold_secs);
 
+        PairPolicy {
+            rules,
+            staleness_threshold: Duration::seconds(threshold_secs as i64),
+            min_sources: rules.map_or(1, |rules| rules.min_sources),
+            aggregation: rules.map_or(AggregationMode::default(), |rules| rules.aggregation),
+        }
+    }
+}
+
+/// Select the served price among a pair's fresh sources
+fn resolve(
+    prices: &HashMap<PriceKey, PriceEntry>,
+    pair: &str,
+    policy: &PairPolicy,
+    now: &DateTime<Utc>,
+) -> Result<Selection, PriceError> {
+    let entries: Vec<&PriceEntry> = prices
+        .iter()
+        .filter(|((p, _), _)| p == pair)
+        .map(|(_, entry)| entry)
+        .collect();
+    if entries.is_empty() {
+        return Err(PriceError::Missing);
+    }
+
+    let fresh: Vec<&PriceEntry> = entries
+        .into_iter()
+        .filter(|entry| is_fresh(&entry.data.timestamp, now, &policy.staleness_threshold))
+        .collect();
+    if fresh.is_empty() {
+        return Err(PriceError::Stale);
+    }
+    if fresh.len() < policy.min_sources {
+        return Err(PriceError::InsufficientSources);
+    }
+
+    match policy.aggregation {
+        AggregationMode::Priority => {
+            let best = fresh
+                .into_iter()
+                .min_by_key(|entry| entry.priority)
+                .expect("at least one fresh entry");
+            Ok(Selection {
+                data: best.data.clone(),
+                fallback_used: best.priority > 1,
+            })
+        }
+        AggregationMode::Median => Ok(Selection {
+            data: median(pair, &fresh),
+            fallback_used: false,
+        }),
+    }
+}
+
+/// Median price of the given entries, timestamped with the oldest of them
+fn median(pair: &str, entries: &[&PriceEntry]) -> PriceData {
+    let mut prices: Vec<Decimal> = entries.iter().map(|entry| entry.data.price).collect();
+    prices.sort_unstable();
+
+    let middle = prices.len() / 2;
+    let price = if prices.len().is_multiple_of(2) {
+        let (lower, upper) = (prices[middle - 1], prices[middle]);
+        // Halving first loses the last digit of precision, so only when the sum overflows
+        lower
+            .checked_add(upper)
+            .map(|sum| sum / Decimal::TWO)
+            .unwrap_or_else(|| lower / Decimal::TWO + upper / Decimal::TWO)
+    } else {
+        prices[middle]
+    };
+    let timestamp = entries
+        .iter()
+        .map(|entry| entry.data.timestamp)
+        .min()
+        .expect("at least one entry");
+
+    PriceData {
+        pair: pair.to_string(),
+        price,
+        source: MEDIAN_SOURCE.to_string(),
+        timestamp,
+        slot: None,
+        price_impact_pct: None,
+    }
+}
+
+/// Reject ticks outside the pair's plausible range or moving too far from
+/// the source's previous fresh price
+fn check_bounds(
+    data: &PriceData,
+    previous: Option<&Decimal>,
+    rules: Option<&PairConfig>,
+) -> Result<(), FeedError> {
+    let Some(rules) = rules else {
+        return Ok(());
+    };
+
+    if let Some(min) = rules.min_price.filter(|min| data.price < *min) {
+        return Err(FeedError::Rejected(format!(
+            "{} is below min_price {}",
+            data.price, min
+        )));
+    }
+    if let Some(max) = rules.max_price.filter(|max| data.price > *max) {
+        return Err(FeedError::Rejected(format!(
+            "{} is above max_price {}",
+            data.price, max
+        )));
+    }
+
+    if let (Some(max_move), Some(previous)) = (rules.max_move_percent, previous) {
+        if !previous.is_zero() {
+            let moved = data
+                .price
+                .checked_sub(*previous)
+                .and_then(|change| change.checked_div(*previous))
+                .and_then(|ratio| ratio.abs().checked_mul(Decimal::ONE_HUNDRED));
+            match moved {
+                Some(moved) if moved <= max_move => {}
+                Some(moved) => {
+                    return Err(FeedError::Rejected(format!(
+                        "moved {}% from {}, above max_move_percent {}",
+                        moved.round_dp(2),
+                        previous,
+                        max_move
+                    )));
+                }
+                // A move too large to represent is certainly out of bounds
+                None => {
+                    return Err(FeedError::Rejected(format!(
+                        "move from {} to {} overflows, above max_move_percent {}",
+                        previous, data.price, max_move
+                    )));
+                }
+            }
+        }
+    }
+
+    Ok(())
 }
 
 fn is_fresh(timestamp: &DateTime<Utc>, now: &DateTime<Utc>, threshold: &Duration) -> bool {
     *now - *timestamp < *threshold
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn price(source: &str, price: &str, age_secs: i64, now: &DateTime<Utc>) -> PriceData {
+        PriceData {
+            pair: "SOL/USDC".to_string(),
+            price: Decimal::from_str(price).unwrap(),
+            source: source.to_string(),
+            timestamp: *now - Duration::seconds(age_secs),
+            slot: None,
+            price_impact_pct: None,
+        }
+    }
+
+    fn prices(entries: &[(PriceData, u32)]) -> HashMap<PriceKey, PriceEntry> {
+        entries
+            .iter()
+            .map(|(data, priority)| {
+                (
+                    (data.pair.clone(), data.source.clone()),
+                    PriceEntry {
+                        data: data.clone(),
+                        priority: *priority,
+                    },
+                )
+            })
+            .collect()
+    }
+
+    fn policy(min_sources: usize, aggregation: AggregationMode) -> PairPolicy<'static> {
+        PairPolicy {
+            rules: None,
+            staleness_threshold: Duration::seconds(30),
+            min_sources,
+            aggregation,
+        }
+    }
+
+    fn rules(min: Option<&str>, max: Option<&str>, max_move: Option<&str>) -> PairConfig {
+        let decimal = |value: Option<&str>| value.map(|value| Decimal::from_str(value).unwrap());
+        PairConfig {
+            pair: "SOL/USDC".to_string(),
+            staleness_threshold_secs: None,
+            min_price: decimal(min),
+            max_price: decimal(max),
+            max_move_percent: decimal(max_move),
+            min_sources: 1,
+            aggregation: AggregationMode::Priority,
+        }
+    }
+
+    #[test]
+    fn priority_serves_the_best_fresh_source() {
+        let now = Utc::now();
+        let prices = prices(&[
+            (price("jupiter", "150", 60, &now), 1),
+            (price("mock", "151", 1, &now), 2),
+            (price("kraken", "152", 1, &now), 3),
+        ]);
+
+        let selection = resolve(
+            &prices,
+            "SOL/USDC",
+            &policy(1, AggregationMode::Priority),
+            &now,
+        )
+        .unwrap();
+        assert_eq!(selection.data.source, "mock");
+        assert!(selection.fallback_used);
+    }
+
+    #[test]
+    fn median_of_fresh_sources() {
+        let now = Utc::now();
+        let mut entries = vec![
+            (price("a", "150", 1, &now), 1),
+            (price("b", "154", 2, &now), 2),
+            (price("c", "151", 3, &now), 3),
+            (price("stale", "1000", 60, &now), 4),
+        ];
+        let median_policy = policy(1, AggregationMode::Median);
+
+        let selection = resolve(&prices(&entries), "SOL/USDC", &median_policy, &now).unwrap();
+        assert_eq!(selection.data.price, Decimal::from(151));
+        assert_eq!(selection.data.source, MEDIAN_SOURCE);
+        assert_eq!(selection.data.timestamp, now - Duration::seconds(3));
+
+        entries.push((price("d", "153", 1, &now), 5));
+        let selection = resolve(&prices(&entries), "SOL/USDC", &median_policy, &now).unwrap();
+        assert_eq!(selection.data.price, Decimal::from(152));
+    }
+
+    #[test]
+    fn median_of_two_near_max_does_not_overflow() {
+        let now = Utc::now();
+        let mut lower = price("a", "0", 1, &now);
+        lower.price = Decimal::MAX - Decimal::ONE;
+        let mut upper = price("b", "0", 1, &now);
+        upper.price = Decimal::MAX;
+
+        let prices = prices(&[(lower, 1), (upper, 2)]);
+        let selection = resolve(
+            &prices,
+            "SOL/USDC",
+            &policy(1, AggregationMode::Median),
+            &now,
+        )
+        .unwrap();
+        assert!(selection.data.price >= Decimal::MAX - Decimal::ONE);
+    }
+
+    #[test]
+    fn min_sources_counts_fresh_sources_only() {
+        let now = Utc::now();
+        let prices = prices(&[
+            (price("a", "150", 1, &now), 1),
+            (price("b", "151", 60, &now), 2),
+        ]);
+
+        assert!(matches!(
+            resolve(
+                &prices,
+                "SOL/USDC",
+                &policy(2, AggregationMode::Priority),
+                &now
+            ),
+            Err(PriceError::InsufficientSources)
+        ));
+        assert!(resolve(
+            &prices,
+            "SOL/USDC",
+            &policy(1, AggregationMode::Priority),
+            &now
+        )
+        .is_ok());
+        assert!(matches!(
+            resolve(
+                &prices,
+                "ETH/USDC",
+                &policy(1, AggregationMode::Priority),
+                &now
+            ),
+            Err(PriceError::Missing)
+        ));
+    }
+
+    #[test]
+    fn check_bounds_rejects_prices_outside_the_range() {
+        let now = Utc::now();
+        let rules = rules(Some("100"), Some("200"), None);
+
+        assert!(check_bounds(&price("a", "150", 0, &now), None, Some(&rules)).is_ok());
+        assert!(matches!(
+            check_bounds(&price("a", "99", 0, &now), None, Some(&rules)),
+            Err(FeedError::Rejected(_))
+        ));
+        assert!(matches!(
+            check_bounds(&price("a", "201", 0, &now), None, Some(&rules)),
+            Err(FeedError::Rejected(_))
+        ));
+        assert!(check_bounds(&price("a", "1", 0, &now), None, None).is_ok());
+    }
+
+    #[test]
+    fn check_bounds_rejects_large_moves() {
+        let now = Utc::now();
+        let rules = rules(None, None, Some("5"));
+        let previous = Decimal::from(100);
+
+        assert!(check_bounds(&price("a", "105", 0, &now), Some(&previous), Some(&rules)).is_ok());
+        assert!(check_bounds(&price("a", "95", 0, &now), Some(&previous), Some(&rules)).is_ok());
+        assert!(matches!(
+            check_bounds(&price("a", "106", 0, &now), Some(&previous), Some(&rules)),
+            Err(FeedError::Rejected(_))
+        ));
+        assert!(check_bounds(&price("a", "500", 0, &now), None, Some(&rules)).is_ok());
+    }
+
+    #[test]
+    fn check_bounds_rejects_moves_that_overflow() {
+        let now = Utc::now();
+        let rules = rules(None, None, Some("5"));
+        let previous = Decimal::from_str("0.0000000000000000000000000001").unwrap();
+        let mut data = price("a", "0", 0, &now);
+        data.price = Decimal::MAX;
+
+        let error = check_bounds(&data, Some(&previous), Some(&rules)).unwrap_err();
+        assert!(matches!(error, FeedError::Rejected(message) if message.contains("overflows")));
+    }
+}